@@ -0,0 +1,249 @@
+use common::{parse_token, ParseError, Solution, SolveError};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, BufRead};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+mod report;
+mod stream;
+
+pub use report::{Contributor, Pairing, ReportFormat};
+pub use stream::{stream, DEFAULT_CHUNK_LEN};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = (Vec<i64>, Vec<i64>);
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one((left, right): &Self::Input) -> Result<i64, SolveError> {
+        part_one(left.clone(), right.clone())
+    }
+
+    fn part_two((left, right): &Self::Input) -> Result<i64, SolveError> {
+        part_two(left.clone(), right.clone())
+    }
+}
+
+/// Integer types that location IDs, distances and scores can be computed in.
+pub trait LocationId:
+    Copy + Ord + Hash + Display + FromStr<Err = ParseIntError> + TryFrom<usize>
+{
+    const ZERO: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! location_id {
+    ($($t:ty),*) => {$(
+        impl LocationId for $t {
+            const ZERO: Self = 0;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+location_id!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+pub type Lists<T> = (Vec<T>, Vec<T>);
+
+pub fn read_input<T: LocationId>(reader: impl BufRead) -> Result<Lists<T>, Box<dyn Error>> {
+    Ok(parse_input(&io::read_to_string(reader)?)?)
+}
+
+/// Parses one pair of IDs per line, separated by any whitespace. Blank lines are ignored.
+pub fn parse_input<T: LocationId>(input: &str) -> Result<Lists<T>, ParseError> {
+    let mut left: Vec<T> = Vec::new();
+    let mut right: Vec<T> = Vec::new();
+
+    for (i, l) in input.lines().enumerate() {
+        if let Some((l_id, r_id)) = parse_line(i + 1, l)? {
+            left.push(l_id);
+            right.push(r_id);
+        }
+    }
+
+    Ok((left, right))
+}
+
+/// Parses line `line_no` of the input, or returns `None` if it is blank.
+pub fn parse_line<T: LocationId>(line_no: usize, line: &str) -> Result<Option<(T, T)>, ParseError> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    match parts[..] {
+        [] => Ok(None),
+        [l_id, r_id] => Ok(Some((
+            parse_token(line_no, line, l_id)?,
+            parse_token(line_no, line, r_id)?,
+        ))),
+        _ => {
+            let message = format!("expected two location IDs, found {}", parts.len());
+            Err(ParseError::at(line_no, line, line.trim(), message))
+        }
+    }
+}
+
+pub fn part_one<T: LocationId>(mut left: Vec<T>, mut right: Vec<T>) -> Result<T, SolveError> {
+    left.sort();
+    right.sort();
+
+    left.iter()
+        .zip(&right)
+        .try_fold(T::ZERO, |total, (&l, &r)| {
+            total.checked_add(distance(l, r)?)
+        })
+        .ok_or_else(|| overflow::<T>("total distance"))
+}
+
+pub fn part_two<T: LocationId>(left: Vec<T>, right: Vec<T>) -> Result<T, SolveError> {
+    let mut counts: HashMap<T, usize> = HashMap::with_capacity(right.len());
+
+    for num in right {
+        *counts.entry(num).or_default() += 1;
+    }
+
+    left.iter()
+        .try_fold(T::ZERO, |total, &num| {
+            let count = T::try_from(counts.get(&num).copied().unwrap_or_default()).ok()?;
+            total.checked_add(num.checked_mul(count)?)
+        })
+        .ok_or_else(|| overflow::<T>("similarity score"))
+}
+
+/// Distance between two IDs, or `None` if it does not fit in `T`.
+fn distance<T: LocationId>(l: T, r: T) -> Option<T> {
+    l.max(r).checked_sub(l.min(r))
+}
+
+fn overflow<T>(what: &str) -> SolveError {
+    SolveError::new(format!(
+        "the {what} overflows {}",
+        std::any::type_name::<T>()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let (left, right) = parse_input::<u32>(include_str!("../data/test")).unwrap();
+
+        let result = part_one(left, right).unwrap();
+
+        assert_eq!(result, 11);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let (left, right) = read_input::<i64>(include_str!("../data/test").as_bytes()).unwrap();
+
+        let result = part_two(left, right).unwrap();
+
+        assert_eq!(result, 31);
+    }
+
+    #[test]
+    fn test_whitespace_separators() {
+        let (left, right) = parse_input::<u32>("3\t4\n4 3  \n\n 2     5\r\n").unwrap();
+
+        assert_eq!((left, right), (vec![3, 4, 2], vec![4, 3, 5]));
+
+        let error = parse_input::<u32>("3   4\n4   3   9").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected two location IDs, found 3");
+    }
+
+    #[test]
+    fn test_pairing_report() {
+        let (left, right) = parse_input::<u32>(include_str!("../data/test")).unwrap();
+
+        let pairing = Pairing::new(left, right, 2).unwrap();
+
+        assert_eq!(pairing.pairs[0], (1, 3, 2));
+        assert_eq!(pairing.max_distance, Some(5));
+        assert_eq!(pairing.median_distance, Some(1));
+        assert_eq!(
+            (pairing.left_only, pairing.right_only),
+            (vec![1, 2], vec![5, 9])
+        );
+        assert_eq!(
+            pairing.contributors[0],
+            Contributor {
+                id: 3,
+                left_count: 3,
+                right_count: 3,
+                score: 27
+            }
+        );
+        assert_eq!(pairing.contributors[1].score, 4);
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = include_str!("../data/test");
+
+        assert_eq!(stream::<u32>(input.as_bytes(), 4).unwrap(), (11, 31));
+        assert_eq!(stream::<u32>(input.as_bytes(), 1).unwrap(), (11, 31));
+        assert_eq!(
+            stream::<u32>(input.as_bytes(), DEFAULT_CHUNK_LEN).unwrap(),
+            (11, 31)
+        );
+    }
+
+    #[test]
+    fn test_stream_merge_passes() {
+        let input: String = (0..200)
+            .map(|i| format!("{}   {}\n", (i * 37) % 101, (i * 53) % 89))
+            .collect();
+        let (left, right) = parse_input::<u64>(&input).unwrap();
+        let expected = (
+            part_one(left.clone(), right.clone()).unwrap(),
+            part_two(left, right).unwrap(),
+        );
+
+        // 200 runs per column, merged three at a time.
+        let streamed = stream::stream_with::<u64>(input.as_bytes(), 1, 3).unwrap();
+
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_signed_ids_and_overflow() {
+        let (left, right) = parse_input::<i64>("-3   4\n9000000000   -2").unwrap();
+
+        assert_eq!(part_one(left, right), Ok(8999999997));
+
+        let result = part_two::<u8>(vec![200], vec![200, 200]);
+
+        assert_eq!(
+            result,
+            Err(SolveError::new("the similarity score overflows u8"))
+        );
+    }
+}
@@ -1,91 +1,118 @@
+use common::{Options, ParseError, Record, Solution};
+use day01::{Day01, Pairing, ReportFormat};
+use std::env;
+use std::error::Error;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::{self, BufRead, BufReader};
+use std::process::ExitCode;
+use std::time::Instant;
 
-fn read_input(path: &str) -> Result<(Vec<u32>, Vec<u32>), Box<dyn std::error::Error>> {
-    let file = File::open(Path::new(path)).expect("no file found");
+const USAGE: &str =
+    "usage: day01 [--part 1|2] [--format text|json] [--report table|csv] [--top N] [--stream [--chunk N]] [FILE]";
 
-    let reader = BufReader::new(file);
+/// Prints the pairing report for the lists in `path` instead of the answers.
+fn report(path: &str, format: ReportFormat, top: usize) -> Result<(), Box<dyn Error>> {
+    let input = common::read_input(path)?;
+    let (left, right) = day01::parse_input::<i64>(&input)
+        .map_err(|e: ParseError| e.with_file(common::source_name(path)))?;
 
-    let mut left: Vec<u32> = Vec::new();
-    let mut right: Vec<u32> = Vec::new();
+    Pairing::new(left, right, top)?.write(&mut io::stdout().lock(), format)?;
 
-    let lines = reader.lines();
+    Ok(())
+}
 
-    for line in lines {
-        let l = line?;
-        let parts: Vec<&str> = l.split("   ").collect();
-        if parts.len() == 2 {
-            left.push(parts[0].trim().parse().unwrap());
-            right.push(parts[1].trim().parse().unwrap());
+/// Solves `path` without loading it into memory: see [`day01::stream`].
+fn stream(path: &str, chunk_len: usize, options: &Options) -> Result<(), Box<dyn Error>> {
+    let reader: Box<dyn BufRead> = if path == common::STDIN {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(
+            File::open(path).map_err(|e| format!("{path}: {e}"))?,
+        ))
+    };
+
+    let start = Instant::now();
+    let (distance, similarity) =
+        day01::stream::<i64>(reader, chunk_len).map_err(|e| match e.downcast::<ParseError>() {
+            Ok(e) => e.with_file(common::source_name(path)).into(),
+            Err(e) => e,
+        })?;
+    let elapsed = start.elapsed();
+
+    for (part, answer) in [(1, distance), (2, similarity)] {
+        if options.wants(part) {
+            let record = Record {
+                day: Day01::DAY,
+                part,
+                answer: answer.to_string(),
+                elapsed,
+            };
+            record.print(options.format);
         }
     }
 
-    Ok((left, right))
+    Ok(())
 }
 
-fn part_one(mut left: Vec<u32>, mut right: Vec<u32>) -> u32{
-    left.sort();
-    right.sort();
-    let mut result: Vec<u32> = Vec::new();
-    for i in 0..left.len() {
-        let l = left[i];
-        let r = right[i];
-
-        if l > r {
-            result.push(l - r)
-        } else {
-            result.push(r - l)
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut options = Options::default();
+    let mut format = None;
+    let mut top = 10;
+    let mut streaming = false;
+    let mut chunk_len = None;
+    let mut files = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--report" => {
+                format = Some(
+                    args.next()
+                        .ok_or("--report expects table or csv")?
+                        .parse()?,
+                )
+            }
+            "--top" => {
+                top = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .ok_or("--top expects a number")?
+            }
+            "--stream" => streaming = true,
+            "--chunk" => {
+                chunk_len = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&n| n > 0)
+                    .map(Some)
+                    .ok_or("--chunk expects a positive number")?
+            }
+            _ if options.parse_flag(&arg, &mut args)? => {}
+            _ => files.push(arg),
         }
     }
-    result.iter().sum::<u32>()
-}
-
-fn part_two(mut left: Vec<u32>, mut right: Vec<u32>) -> u32 {
-    left.sort();
-    right.sort();
 
-    let mut v2: Vec<u32> = Vec::new();
+    let path = match files.as_slice() {
+        [] => common::STDIN,
+        [path] => path,
+        _ => return Err(USAGE.into()),
+    };
 
-    for num in left {
-        v2.push(num * right.iter().filter(|&n| *n == num).count() as u32);
-        right.iter().filter(|&n| *n == num).count();
+    if chunk_len.is_some() && !streaming {
+        return Err("--chunk only applies with --stream".into());
     }
 
-    v2.iter().sum()
-}
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let (left, right) = read_input("./data/input")?;
-
-    part_one(left.clone(), right.clone());
-
-    part_two(left, right);
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-
-        let (left, right) = read_input("./data/test").unwrap();
-        
-        let result = part_one(left,right);
-        
-        assert_eq!(result, 11);
+    match (format, streaming) {
+        (Some(_), true) => Err("--report cannot be combined with --stream".into()),
+        (Some(format), false) => report(path, format, top),
+        (None, true) => stream(
+            path,
+            chunk_len.unwrap_or(day01::DEFAULT_CHUNK_LEN),
+            &options,
+        ),
+        (None, false) => common::run_file::<Day01>(path, &options),
     }
+}
 
-    #[test]
-    fn test_part_two() {
-
-        let (left, right) = read_input("./data/test").unwrap();
-
-        let result = part_two(left,right);
-
-        assert_eq!(result, 31);
-    }
+fn main() -> ExitCode {
+    common::exit(run(env::args().skip(1)))
 }
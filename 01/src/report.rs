@@ -0,0 +1,206 @@
+use crate::{distance, overflow, LocationId};
+use common::SolveError;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// How a [`Pairing`] report is printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Aligned columns, for people.
+    #[default]
+    Table,
+    /// Comma-separated sections, each with a header row, for spreadsheets.
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(ReportFormat::Table),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(format!(
+                "unknown report format `{s}`, expected table or csv"
+            )),
+        }
+    }
+}
+
+/// How much one distinct left ID adds to the similarity score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contributor<T> {
+    pub id: T,
+    pub left_count: usize,
+    pub right_count: usize,
+    pub score: T,
+}
+
+/// The sorted matching behind [`part_one`](crate::part_one), with metrics on both lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pairing<T> {
+    /// `(left, right, distance)` for each pair, smallest IDs first.
+    pub pairs: Vec<(T, T, T)>,
+    pub max_distance: Option<T>,
+    /// Lower median of the distances.
+    pub median_distance: Option<T>,
+    /// Distinct IDs that never appear in the right list.
+    pub left_only: Vec<T>,
+    /// Distinct IDs that never appear in the left list.
+    pub right_only: Vec<T>,
+    /// The `top` largest similarity contributors, largest first.
+    pub contributors: Vec<Contributor<T>>,
+}
+
+impl<T: LocationId> Pairing<T> {
+    pub fn new(mut left: Vec<T>, mut right: Vec<T>, top: usize) -> Result<Self, SolveError> {
+        left.sort();
+        right.sort();
+
+        let pairs: Vec<(T, T, T)> = left
+            .iter()
+            .zip(&right)
+            .map(|(&l, &r)| Some((l, r, distance(l, r)?)))
+            .collect::<Option<_>>()
+            .ok_or_else(|| overflow::<T>("distance"))?;
+
+        let mut distances: Vec<T> = pairs.iter().map(|&(_, _, d)| d).collect();
+        distances.sort();
+
+        let left_counts = counts(&left);
+        let right_counts = counts(&right);
+
+        let mut contributors = Vec::new();
+        for (&id, &left_count) in &left_counts {
+            let Some(&right_count) = right_counts.get(&id) else {
+                continue;
+            };
+            let score = T::try_from(left_count * right_count)
+                .ok()
+                .and_then(|count| id.checked_mul(count))
+                .ok_or_else(|| overflow::<T>("similarity score"))?;
+            contributors.push(Contributor {
+                id,
+                left_count,
+                right_count,
+                score,
+            });
+        }
+        contributors.sort_by(|a, b| b.score.cmp(&a.score).then(a.id.cmp(&b.id)));
+        contributors.truncate(top);
+
+        Ok(Pairing {
+            pairs,
+            max_distance: distances.last().copied(),
+            median_distance: distances
+                .get(distances.len().saturating_sub(1) / 2)
+                .copied(),
+            left_only: only_in(&left_counts, &right_counts),
+            right_only: only_in(&right_counts, &left_counts),
+            contributors,
+        })
+    }
+
+    pub fn write(&self, out: &mut impl Write, format: ReportFormat) -> io::Result<()> {
+        match format {
+            ReportFormat::Table => self.write_table(out),
+            ReportFormat::Csv => self.write_csv(out),
+        }
+    }
+
+    fn write_table(&self, out: &mut impl Write) -> io::Result<()> {
+        let width = self
+            .pairs
+            .iter()
+            .flat_map(|(l, r, d)| [l, r, d])
+            .map(|n| n.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max("distance".len());
+
+        writeln!(
+            out,
+            "{:>width$}  {:>width$}  {:>width$}",
+            "left", "right", "distance"
+        )?;
+        for (l, r, d) in &self.pairs {
+            writeln!(out, "{l:>width$}  {r:>width$}  {d:>width$}")?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "max distance:    {}", optional(&self.max_distance))?;
+        writeln!(out, "median distance: {}", optional(&self.median_distance))?;
+        writeln!(out, "left only:       {}", joined(&self.left_only, ", "))?;
+        writeln!(out, "right only:      {}", joined(&self.right_only, ", "))?;
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "top {} similarity contributors:",
+            self.contributors.len()
+        )?;
+        for c in &self.contributors {
+            writeln!(
+                out,
+                "{:>width$}  x{} left  x{} right  = {}",
+                c.id, c.left_count, c.right_count, c.score
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "left,right,distance")?;
+        for (l, r, d) in &self.pairs {
+            writeln!(out, "{l},{r},{d}")?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "metric,value")?;
+        writeln!(out, "max_distance,{}", optional(&self.max_distance))?;
+        writeln!(out, "median_distance,{}", optional(&self.median_distance))?;
+        writeln!(out, "left_only,{}", joined(&self.left_only, " "))?;
+        writeln!(out, "right_only,{}", joined(&self.right_only, " "))?;
+
+        writeln!(out)?;
+        writeln!(out, "id,left_count,right_count,score")?;
+        for c in &self.contributors {
+            writeln!(
+                out,
+                "{},{},{},{}",
+                c.id, c.left_count, c.right_count, c.score
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn counts<T: LocationId>(ids: &[T]) -> BTreeMap<T, usize> {
+    let mut counts = BTreeMap::new();
+    for &id in ids {
+        *counts.entry(id).or_default() += 1;
+    }
+    counts
+}
+
+fn only_in<T: LocationId>(these: &BTreeMap<T, usize>, others: &BTreeMap<T, usize>) -> Vec<T> {
+    these
+        .keys()
+        .filter(|id| !others.contains_key(id))
+        .copied()
+        .collect()
+}
+
+fn optional<T: LocationId>(value: &Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn joined<T: LocationId>(ids: &[T], separator: &str) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
@@ -0,0 +1,287 @@
+use crate::{distance, overflow, parse_line, LocationId};
+use std::cmp::{Ordering as Order, Reverse};
+use std::collections::BinaryHeap;
+use std::env;
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, ErrorKind, Lines, Write};
+use std::path::PathBuf;
+use std::process;
+use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of IDs per column held in memory before a sorted run is spilled to disk.
+pub const DEFAULT_CHUNK_LEN: usize = 1 << 20;
+
+/// Most run files of one column that are open at once while merging.
+pub const MERGE_FAN_IN: usize = 64;
+
+/// Solves both parts in one pass over `reader` without holding the lists in memory.
+///
+/// Each column is sorted externally: every `chunk_len` IDs are sorted and spilled to a
+/// temporary file, and groups of at most [`MERGE_FAN_IN`] runs are merged into longer runs
+/// until a single merge can read them all. The sorted columns are then read twice: side by
+/// side for the distance sum, and grouped by ID for the similarity score. Memory use is
+/// bounded by `chunk_len` and the fan-in, not by the length of the input.
+pub fn stream<T: LocationId>(
+    reader: impl BufRead,
+    chunk_len: usize,
+) -> Result<(T, T), Box<dyn Error>> {
+    stream_with(reader, chunk_len, MERGE_FAN_IN)
+}
+
+pub(crate) fn stream_with<T: LocationId>(
+    reader: impl BufRead,
+    chunk_len: usize,
+    fan_in: usize,
+) -> Result<(T, T), Box<dyn Error>> {
+    let chunk_len = chunk_len.max(1);
+    let fan_in = fan_in.max(2);
+    let mut left = Column::new(chunk_len);
+    let mut right = Column::new(chunk_len);
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let Some((l, r)) = parse_line(i + 1, &line)? else {
+            continue;
+        };
+        left.push(l)?;
+        right.push(r)?;
+    }
+
+    let left = left.finish(fan_in)?;
+    let right = right.finish(fan_in)?;
+
+    let mut total_distance = T::ZERO;
+    for pair in left.merge()?.zip(right.merge()?) {
+        let (l, r) = (pair.0?, pair.1?);
+        total_distance = distance(l, r)
+            .and_then(|d| total_distance.checked_add(d))
+            .ok_or_else(|| overflow::<T>("total distance"))?;
+    }
+
+    let mut similarity = T::ZERO;
+    let mut left_groups = Groups::new(left.merge()?);
+    let mut right_groups = Groups::new(right.merge()?);
+    let mut l = left_groups.next().transpose()?;
+    let mut r = right_groups.next().transpose()?;
+    while let (Some((l_id, l_count)), Some((r_id, r_count))) = (l, r) {
+        match l_id.cmp(&r_id) {
+            Order::Less => l = left_groups.next().transpose()?,
+            Order::Greater => r = right_groups.next().transpose()?,
+            Order::Equal => {
+                similarity = l_count
+                    .checked_mul(r_count)
+                    .and_then(|count| T::try_from(count).ok())
+                    .and_then(|count| l_id.checked_mul(count))
+                    .and_then(|score| similarity.checked_add(score))
+                    .ok_or_else(|| overflow::<T>("similarity score"))?;
+                l = left_groups.next().transpose()?;
+                r = right_groups.next().transpose()?;
+            }
+        }
+    }
+
+    Ok((total_distance, similarity))
+}
+
+/// One list, kept as sorted runs on disk plus the chunk still being filled.
+struct Column<T> {
+    chunk: Vec<T>,
+    chunk_len: usize,
+    runs: Vec<Run>,
+}
+
+impl<T: LocationId> Column<T> {
+    fn new(chunk_len: usize) -> Self {
+        Column {
+            chunk: Vec::new(),
+            chunk_len,
+            runs: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, id: T) -> Result<(), Box<dyn Error>> {
+        self.chunk.push(id);
+        if self.chunk.len() >= self.chunk_len {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self) -> Result<(), Box<dyn Error>> {
+        self.chunk.sort_unstable();
+        let run = write_run(self.chunk.drain(..).map(Ok))?;
+        self.runs.push(run);
+        Ok(())
+    }
+
+    /// Sorts the column, merging its runs until there are at most `fan_in` of them.
+    fn finish(mut self, fan_in: usize) -> Result<Sorted<T>, Box<dyn Error>> {
+        if self.runs.is_empty() {
+            self.chunk.sort_unstable();
+        } else if !self.chunk.is_empty() {
+            self.spill()?;
+        }
+
+        let mut runs = self.runs;
+        while runs.len() > fan_in {
+            runs = runs
+                .chunks(fan_in)
+                .map(|group| write_run(merge::<T>(group, &[])?))
+                .collect::<Result<_, _>>()?;
+        }
+
+        Ok(Sorted {
+            runs,
+            chunk: self.chunk,
+        })
+    }
+}
+
+/// A sorted column: either a few runs on disk, or a single chunk that never had to be spilled.
+struct Sorted<T> {
+    runs: Vec<Run>,
+    chunk: Vec<T>,
+}
+
+impl<T: LocationId> Sorted<T> {
+    /// All IDs of the column in ascending order.
+    fn merge(&self) -> Result<Merge<'_, T>, Box<dyn Error>> {
+        merge(&self.runs, &self.chunk)
+    }
+}
+
+/// Merges the sorted `runs` and the sorted `chunk`, opening every run at once.
+fn merge<'a, T: LocationId>(runs: &[Run], chunk: &'a [T]) -> Result<Merge<'a, T>, Box<dyn Error>> {
+    let mut sources = Vec::with_capacity(runs.len());
+    for run in runs {
+        sources.push(BufReader::new(File::open(&run.path)?).lines());
+    }
+
+    let mut merge = Merge {
+        heap: BinaryHeap::new(),
+        sources,
+        chunk: chunk.iter(),
+    };
+    for source in 0..merge.sources.len() {
+        merge.refill(source)?;
+    }
+    merge.refill(usize::MAX)?;
+
+    Ok(merge)
+}
+
+/// Writes the sorted `ids` to a new run file.
+fn write_run<T: LocationId>(
+    ids: impl Iterator<Item = Result<T, Box<dyn Error>>>,
+) -> Result<Run, Box<dyn Error>> {
+    let (run, file) = Run::create()?;
+    let mut out = BufWriter::new(file);
+    for id in ids {
+        writeln!(out, "{}", id?)?;
+    }
+    out.flush()?;
+    Ok(run)
+}
+
+/// A k-way merge of sorted runs and an in-memory chunk.
+struct Merge<'a, T> {
+    /// Smallest unread ID of each source, tagged with its source; `usize::MAX` is the chunk.
+    heap: BinaryHeap<Reverse<(T, usize)>>,
+    sources: Vec<Lines<BufReader<File>>>,
+    chunk: slice::Iter<'a, T>,
+}
+
+impl<T: LocationId> Merge<'_, T> {
+    fn refill(&mut self, source: usize) -> Result<(), Box<dyn Error>> {
+        let next = if source == usize::MAX {
+            self.chunk.next().copied()
+        } else {
+            match self.sources[source].next() {
+                Some(line) => Some(line?.parse()?),
+                None => None,
+            }
+        };
+        if let Some(id) = next {
+            self.heap.push(Reverse((id, source)));
+        }
+        Ok(())
+    }
+}
+
+impl<T: LocationId> Iterator for Merge<'_, T> {
+    type Item = Result<T, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((id, source)) = self.heap.pop()?;
+        Some(self.refill(source).map(|()| id))
+    }
+}
+
+/// Each distinct ID of a sorted stream with how often it occurs.
+struct Groups<I: Iterator> {
+    ids: I,
+    pending: Option<I::Item>,
+}
+
+impl<I: Iterator> Groups<I> {
+    fn new(ids: I) -> Self {
+        Groups { ids, pending: None }
+    }
+}
+
+impl<T: LocationId, I: Iterator<Item = Result<T, Box<dyn Error>>>> Iterator for Groups<I> {
+    type Item = Result<(T, usize), Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = match self.pending.take().or_else(|| self.ids.next())? {
+            Ok(id) => id,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let mut count = 1;
+        for next in self.ids.by_ref() {
+            match next {
+                Ok(next) if next == id => count += 1,
+                next => {
+                    self.pending = Some(next);
+                    break;
+                }
+            }
+        }
+
+        Some(Ok((id, count)))
+    }
+}
+
+/// A temporary file holding one sorted run, removed when dropped.
+struct Run {
+    path: PathBuf,
+}
+
+impl Run {
+    /// Creates a new, empty run file in the temporary directory.
+    ///
+    /// The file must not exist yet, so a symlink or a stale file planted at the same path is
+    /// never followed or truncated; the next name is tried instead.
+    fn create() -> io::Result<(Self, File)> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+        loop {
+            let n = NEXT.fetch_add(1, Ordering::Relaxed);
+            let path = env::temp_dir().join(format!("day01-{}-{n}.run", process::id()));
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((Run { path }, file)),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,520 @@
+use common::{parse_token, ParseError, Solution, SolveError};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Formatter};
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Report>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Vec<Report>, ParseError> {
+        parse_reports(input)
+    }
+
+    fn part_one(reports: &Vec<Report>) -> Result<usize, SolveError> {
+        Ok(part_one(reports, &SafetyPolicy::default()))
+    }
+
+    fn part_two(reports: &Vec<Report>) -> Result<usize, SolveError> {
+        Ok(part_two(reports, &SafetyPolicy::default()))
+    }
+}
+
+/// The rules a report has to follow to be safe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    /// Smallest allowed difference between adjacent levels that are not equal.
+    pub min_step: u16,
+    /// Largest allowed difference between adjacent levels.
+    pub max_step: u16,
+    /// Whether equal adjacent levels make a report unsafe; otherwise they are allowed whatever
+    /// `min_step` is.
+    pub strict: bool,
+    /// How many levels the Problem Dampener may remove in part two.
+    pub removals: usize,
+}
+
+impl Default for SafetyPolicy {
+    /// The rules from the puzzle: strictly monotonic, steps of 1 to 3, one removal.
+    fn default() -> Self {
+        SafetyPolicy {
+            min_step: 1,
+            max_step: 3,
+            strict: true,
+            removals: 1,
+        }
+    }
+}
+
+impl SafetyPolicy {
+    /// Consumes `arg`, and its value from `args`, if it is one of the policy flags:
+    /// `--min-step N`, `--max-step N`, `--non-strict` (allow equal adjacent levels) or
+    /// `--removals N`.
+    pub fn parse_flag(
+        &mut self,
+        arg: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<bool, Box<dyn Error>> {
+        fn number<T: FromStr>(
+            flag: &str,
+            args: &mut impl Iterator<Item = String>,
+        ) -> Result<T, String> {
+            args.next()
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| format!("{flag} expects a number"))
+        }
+
+        match arg {
+            "--min-step" => self.min_step = number(arg, args)?,
+            "--max-step" => self.max_step = number(arg, args)?,
+            "--removals" => self.removals = number(arg, args)?,
+            "--non-strict" => self.strict = false,
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Like [`check_levels`], under this policy.
+    pub fn check(&self, levels: &[u16]) -> Result<(), Violation> {
+        let mut increasing = None;
+
+        for position in 1..levels.len() {
+            let (previous, level) = (levels[position - 1], levels[position]);
+            if increasing.is_none() && (self.strict || level != previous) {
+                increasing = Some(level > previous);
+            }
+
+            if let Some(reason) = self.violation(previous, level, increasing.unwrap_or(true)) {
+                return Err(Violation {
+                    reason,
+                    position,
+                    previous,
+                    level,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether `levels` is safe once at most [`removals`](Self::removals) levels are removed.
+    pub fn is_safe(&self, levels: &[u16]) -> bool {
+        self.verdict(levels).is_safe()
+    }
+
+    /// Why `levels` is or is not safe, and which levels the dampener has to remove.
+    pub fn verdict(&self, levels: &[u16]) -> Verdict {
+        match self.check(levels) {
+            Ok(()) => Verdict::Safe,
+            Err(violation) => match self.removals_within(levels, self.removals) {
+                Some(removed) => Verdict::Dampened(removed),
+                None => Verdict::Unsafe(violation),
+            },
+        }
+    }
+
+    /// Indices of the fewest levels whose removal makes `levels` safe, however many that is.
+    ///
+    /// This is the complement of the longest safe subsequence of `levels`, found in O(n^2).
+    pub fn min_removals(&self, levels: &[u16]) -> Vec<usize> {
+        // Every report of at most one level is safe, so this always finds an answer.
+        self.removals_within(levels, levels.len())
+            .unwrap_or_default()
+    }
+
+    /// Fewest levels, at most `limit`, whose removal makes `levels` safe, by index.
+    ///
+    /// For each direction, `removed[l]` is the fewest removals that leave a safe report ending
+    /// at level `l`, and `from[l]` the level kept before it. Only the `limit + 1` levels before
+    /// `l` can precede it, so this takes O(n * limit) steps.
+    fn removals_within(&self, levels: &[u16], limit: usize) -> Option<Vec<usize>> {
+        let n = levels.len();
+        let mut best: Option<(usize, Vec<usize>)> = None;
+
+        for increasing in [true, false] {
+            let mut removed = vec![usize::MAX; n];
+            let mut from = vec![None; n];
+
+            for l in 0..n {
+                removed[l] = l;
+                for j in l.saturating_sub(limit + 1)..l {
+                    if removed[j] != usize::MAX
+                        && removed[j] + (l - j - 1) < removed[l]
+                        && self.violation(levels[j], levels[l], increasing).is_none()
+                    {
+                        removed[l] = removed[j] + (l - j - 1);
+                        from[l] = Some(j);
+                    }
+                }
+                if removed[l] > limit {
+                    removed[l] = usize::MAX;
+                }
+            }
+
+            let Some((total, last)) = (0..n)
+                .filter(|&l| removed[l] != usize::MAX)
+                .map(|l| (removed[l] + (n - 1 - l), l))
+                .min()
+            else {
+                return Some(Vec::new());
+            };
+            if total > limit || best.as_ref().is_some_and(|(best, _)| *best <= total) {
+                continue;
+            }
+
+            let mut kept = vec![false; n];
+            let mut level = Some(last);
+            while let Some(l) = level {
+                kept[l] = true;
+                level = from[l];
+            }
+            best = Some((total, (0..n).filter(|&i| !kept[i]).collect()));
+        }
+
+        best.map(|(_, removed)| removed)
+    }
+
+    /// What is wrong with `level` following `previous` in a report going in the given
+    /// direction, if anything.
+    fn violation(&self, previous: u16, level: u16, increasing: bool) -> Option<Unsafety> {
+        let step = previous.abs_diff(level);
+
+        if step == 0 {
+            self.strict.then_some(Unsafety::ZeroStep)
+        } else if step < self.min_step {
+            Some(Unsafety::StepTooSmall)
+        } else if step > self.max_step {
+            Some(Unsafety::StepTooLarge)
+        } else if (level > previous) != increasing {
+            Some(Unsafety::DirectionChange)
+        } else {
+            None
+        }
+    }
+}
+
+/// Why two adjacent levels make a report unsafe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unsafety {
+    /// The levels go the other way than the ones before them.
+    DirectionChange,
+    /// The levels are equal under a strict policy.
+    ZeroStep,
+    /// The levels differ by less than the policy's `min_step`.
+    StepTooSmall,
+    /// The levels differ by more than the policy's `max_step`.
+    StepTooLarge,
+}
+
+impl fmt::Display for Unsafety {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Unsafety::DirectionChange => "direction change",
+            Unsafety::ZeroStep => "zero step",
+            Unsafety::StepTooSmall => "step too small",
+            Unsafety::StepTooLarge => "step too large",
+        })
+    }
+}
+
+/// The first pair of adjacent levels that makes a report unsafe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub reason: Unsafety,
+    /// Index of the second level of the pair.
+    pub position: usize,
+    pub previous: u16,
+    pub level: u16,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} from level {} to {} ({} -> {})",
+            self.reason,
+            self.position,
+            self.position + 1,
+            self.previous,
+            self.level
+        )
+    }
+}
+
+/// Whether a report is safe under a [`SafetyPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    /// Safe once the levels at these indices are removed.
+    Dampened(Vec<usize>),
+    /// Unsafe even with the dampener; the violation is the first one in the full report.
+    Unsafe(Violation),
+}
+
+impl Verdict {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Verdict::Unsafe(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    /// Level numbers are 1-based, like line numbers.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Safe => write!(f, "safe"),
+            Verdict::Dampened(removed) => {
+                let levels: Vec<String> = removed.iter().map(|i| (i + 1).to_string()).collect();
+                write!(f, "safe after removing level {}", levels.join(", "))
+            }
+            Verdict::Unsafe(violation) => write!(f, "unsafe: {violation}"),
+        }
+    }
+}
+
+/// One line of the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Line of the input the report was read from.
+    pub line: usize,
+    pub levels: Vec<u16>,
+}
+
+pub fn read_reports(reader: impl BufRead) -> Result<Vec<Report>, Box<dyn Error>> {
+    Ok(parse_reports(&io::read_to_string(reader)?)?)
+}
+
+/// Parses one report per line, skipping blank lines.
+pub fn parse_reports(input: &str) -> Result<Vec<Report>, ParseError> {
+    let mut reports = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let levels = parse_levels(i + 1, line)?;
+        if !levels.is_empty() {
+            reports.push(Report {
+                line: i + 1,
+                levels,
+            });
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Parses the space-separated levels of line `line_no` of the input.
+///
+/// Any run of whitespace separates levels. A report with a single level has no adjacent
+/// levels to break the rules, so it is safe; a blank line holds no report at all.
+pub fn parse_levels(line_no: usize, line: &str) -> Result<Vec<u16>, ParseError> {
+    line.split_whitespace()
+        .map(|level| parse_token(line_no, line, level))
+        .collect()
+}
+
+pub fn part_one(reports: &[Report], policy: &SafetyPolicy) -> usize {
+    reports
+        .iter()
+        .filter(|report| policy.check(&report.levels).is_ok())
+        .count()
+}
+
+pub fn part_two(reports: &[Report], policy: &SafetyPolicy) -> usize {
+    reports
+        .iter()
+        .filter(|report| policy.is_safe(&report.levels))
+        .count()
+}
+
+/// The verdict on each report under `policy`, by line number.
+pub fn verdicts(reports: &[Report], policy: &SafetyPolicy) -> Vec<(usize, Verdict)> {
+    reports
+        .iter()
+        .map(|report| (report.line, policy.verdict(&report.levels)))
+        .collect()
+}
+
+/// How many reports need each number of removals to become safe under `policy`, ignoring
+/// its limit on removals.
+pub fn removal_histogram(reports: &[Report], policy: &SafetyPolicy) -> BTreeMap<usize, usize> {
+    let mut histogram = BTreeMap::new();
+
+    for report in reports {
+        *histogram
+            .entry(policy.min_removals(&report.levels).len())
+            .or_default() += 1;
+    }
+
+    histogram
+}
+
+/// Whether `levels` is safe once at most one level is removed, under the puzzle's rules.
+pub fn is_safe_with_dampener(levels: &[u16]) -> bool {
+    SafetyPolicy::default().is_safe(levels)
+}
+
+/// Finds the first adjacent levels that break the puzzle's rules, if any.
+pub fn check_levels(levels: &[u16]) -> Result<(), Violation> {
+    SafetyPolicy::default().check(levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let reports = parse_reports(include_str!("../data/test")).unwrap();
+
+        let result = part_one(&reports, &SafetyPolicy::default());
+
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let reports = read_reports(include_str!("../data/test").as_bytes()).unwrap();
+
+        let result = part_two(&reports, &SafetyPolicy::default());
+
+        assert_eq!(result, 4);
+    }
+
+    #[test]
+    fn test_dampener_candidates() {
+        let brute_force = |levels: &[u16]| {
+            (0..levels.len()).any(|i| {
+                let mut levels = levels.to_vec();
+                levels.remove(i);
+                check_levels(&levels).is_ok()
+            })
+        };
+
+        for levels in [
+            &[1, 3, 2, 1][..],
+            &[5, 1, 6, 7, 8],
+            &[1, 2, 3, 4, 9],
+            &[9, 1, 2, 3, 4],
+            &[1, 5, 2, 3],
+            &[3, 3, 3],
+            &[1, 2, 1, 2],
+        ] {
+            assert_eq!(
+                is_safe_with_dampener(levels),
+                brute_force(levels),
+                "{levels:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let reports = parse_reports(include_str!("../data/test")).unwrap();
+
+        let verdicts = verdicts(&reports, &SafetyPolicy::default());
+
+        assert_eq!(verdicts[0], (1, Verdict::Safe));
+        assert_eq!(
+            verdicts[1].1,
+            Verdict::Unsafe(Violation {
+                reason: Unsafety::StepTooLarge,
+                position: 2,
+                previous: 2,
+                level: 7
+            })
+        );
+        assert_eq!(verdicts[3].1, Verdict::Dampened(vec![2]));
+        assert_eq!(verdicts[3].1.to_string(), "safe after removing level 3");
+        assert_eq!(
+            verdicts[1].1.to_string(),
+            "unsafe: step too large from level 2 to 3 (2 -> 7)"
+        );
+    }
+
+    #[test]
+    fn test_min_removals() {
+        let policy = SafetyPolicy::default();
+
+        assert_eq!(policy.min_removals(&[1, 2, 7, 8, 9]), vec![0, 1]);
+        assert_eq!(policy.min_removals(&[9, 1, 2, 3, 20, 4]), vec![0, 4]);
+        assert_eq!(policy.min_removals(&[5, 5, 5, 5]), vec![1, 2, 3]);
+        assert_eq!(policy.min_removals(&[]), Vec::<usize>::new());
+
+        let reports = parse_reports(include_str!("../data/test")).unwrap();
+
+        assert_eq!(
+            removal_histogram(&reports, &policy),
+            BTreeMap::from([(0, 2), (1, 2), (2, 2)])
+        );
+    }
+
+    #[test]
+    fn test_degenerate_reports() {
+        let input = "7  6 4\n\n5\n \t\n1 2\n";
+
+        let reports = parse_reports(input).unwrap();
+        let verdicts = verdicts(&reports, &SafetyPolicy::default());
+
+        assert_eq!(
+            verdicts.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+            vec![1, 3, 5]
+        );
+        assert!(verdicts
+            .iter()
+            .all(|(_, verdict)| *verdict == Verdict::Safe));
+        assert_eq!(check_levels(&[]), Ok(()));
+
+        let error = Day02::parse("1 2 3\n4 x 6").unwrap_err();
+
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "x"));
+    }
+
+    #[test]
+    fn test_safety_policy() {
+        let reports = parse_reports(include_str!("../data/test")).unwrap();
+        let lenient = SafetyPolicy {
+            max_step: 4,
+            strict: false,
+            removals: 2,
+            ..SafetyPolicy::default()
+        };
+
+        assert_eq!(part_one(&reports, &lenient), 4);
+        assert_eq!(part_two(&reports, &lenient), 6);
+
+        let flat = SafetyPolicy {
+            min_step: 0,
+            ..lenient
+        };
+
+        assert!(flat.is_safe(&[2, 1, 1, 3, 4]));
+        assert_eq!(flat.check(&[2, 2, 5, 4]).unwrap_err().position, 3);
+
+        let mut policy = SafetyPolicy::default();
+        let mut args = ["70000".to_string()].into_iter();
+
+        assert!(policy.parse_flag("--removals", &mut args).unwrap());
+        assert_eq!(policy.removals, 70000);
+    }
+
+    #[test]
+    fn test_non_strict() {
+        let policy = SafetyPolicy {
+            strict: false,
+            ..SafetyPolicy::default()
+        };
+
+        assert_eq!(policy.check(&[1, 1, 1, 1]), Ok(()));
+        assert_eq!(policy.check(&[8, 6, 4, 4, 1]), Ok(()));
+        assert_eq!(policy.check(&[3, 3, 4, 4, 2]).unwrap_err().position, 4);
+        assert_eq!(
+            SafetyPolicy::default().check(&[1, 1]).unwrap_err().reason,
+            Unsafety::ZeroStep
+        );
+    }
+}
@@ -1,114 +1,111 @@
+use common::{Format, Options, ParseError, Record, Solution};
+use day02::{Day02, SafetyPolicy};
+use std::collections::BTreeMap;
+use std::env;
 use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-
-fn part_one(file: File) -> i32 {
-    let reader = BufReader::new(file);
-
-    let mut safe = 0;
-
-    for line in reader.lines() {
-        let levels: Vec<u16> = line
-            .unwrap()
-            .split(" ")
-            .map(|x| x.parse().unwrap())
-            .collect();
-
-        if check_levels(&levels).0 == 0 {
-            safe += 1;
+use std::process::ExitCode;
+use std::time::Instant;
+
+const USAGE: &str = "usage: day02 [--part 1|2] [--format text|json] \
+    [--min-step N] [--max-step N] [--non-strict] [--removals N] [--explain | --histogram] [FILE]";
+
+/// Prints how many reports need each number of removals, with a bar scaled to 50 columns.
+fn print_histogram(histogram: &BTreeMap<usize, usize>, format: Format) {
+    let most = histogram.values().copied().max().unwrap_or(0).max(1);
+
+    for (&removals, &reports) in histogram {
+        match format {
+            Format::Text => println!(
+                "{removals:>3} removals: {reports:>6} {}",
+                "#".repeat((reports * 50).div_ceil(most))
+            ),
+            Format::Json => println!(r#"{{"removals":{removals},"reports":{reports}}}"#),
         }
     }
-
-    safe
 }
-fn part_two(file: File) -> i32 {
-    let reader = BufReader::new(file);
-
-    let mut safe = 0;
-
-    for line in reader.lines() {
-        let levels: Vec<u16> = line
-            .unwrap()
-            .split(" ")
-            .map(|x| x.parse().unwrap())
-            .collect();
 
-        if is_safe_with_dampener(&levels) {
-            safe += 1;
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut options = Options::default();
+    let mut policy = SafetyPolicy::default();
+    let mut explain = false;
+    let mut histogram = false;
+    let mut files = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if arg == "--explain" {
+            explain = true;
+        } else if arg == "--histogram" {
+            histogram = true;
+        } else if !options.parse_flag(&arg, &mut args)? && !policy.parse_flag(&arg, &mut args)? {
+            files.push(arg);
         }
     }
 
-    safe
-}
-fn is_safe_with_dampener(levels: &Vec<u16>) -> bool {
-    if check_levels(levels).0 == 0 {
-        return true;
+    if policy.min_step > policy.max_step {
+        return Err("--min-step cannot be larger than --max-step".into());
     }
-
-    for i in 0..levels.len() {
-        let mut corrected_lvl = levels.clone();
-        corrected_lvl.remove(i);
-        if check_levels(&corrected_lvl).0 == 0 {
-            return true;
-        }
+    if explain && histogram {
+        return Err("--explain cannot be combined with --histogram".into());
+    }
+    if (explain || histogram) && options.part.is_some() {
+        let mode = if explain { "--explain" } else { "--histogram" };
+        return Err(format!("--part cannot be combined with {mode}").into());
     }
 
-    false
-}
-fn check_levels(levels: &[u16]) -> (u16, usize) {
-    let increasing: bool = levels[1] > levels[0];
-
-    let mut unsafe_index = 0;
-    let mut nr_of_unsafe = 0;
+    let path = match files.as_slice() {
+        [] => common::STDIN,
+        [path] => path,
+        _ => return Err(USAGE.into()),
+    };
 
-    for i in 1..levels.len() {
-        let abs = levels[i - 1].abs_diff(levels[i]);
+    let input = common::read_input(path)?;
+    let reports = day02::parse_reports(&input)
+        .map_err(|e: ParseError| e.with_file(common::source_name(path)))?;
 
-        if (increasing && levels[i] < levels[i - 1]) || (!increasing && levels[i] > levels[i - 1]) {
-            nr_of_unsafe += 1;
-            unsafe_index = i;
-            break;
-        }
+    if histogram {
+        print_histogram(&day02::removal_histogram(&reports, &policy), options.format);
+        return Ok(());
+    }
 
-        if abs > 3 || abs == 0 {
-            nr_of_unsafe += 1;
-            unsafe_index = i;
-            break;
+    if explain {
+        for (line, verdict) in day02::verdicts(&reports, &policy) {
+            match options.format {
+                Format::Text => println!("{line}: {verdict}"),
+                Format::Json => println!(
+                    r#"{{"line":{},"safe":{},"verdict":{}}}"#,
+                    line,
+                    verdict.is_safe(),
+                    common::json_string(&verdict.to_string())
+                ),
+            }
         }
+        return Ok(());
     }
 
-    (nr_of_unsafe, unsafe_index)
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let file = File::open(Path::new("./data/input"))?;
+    if policy == SafetyPolicy::default() {
+        return common::solve::<Day02>(&reports, &options);
+    }
 
-    println!("{}", part_one(file.try_clone().unwrap()));
-    println!("{}", part_two(file));
+    for part in [1, 2] {
+        if options.wants(part) {
+            let start = Instant::now();
+            let answer = match part {
+                1 => day02::part_one(&reports, &policy),
+                _ => day02::part_two(&reports, &policy),
+            };
+            let record = Record {
+                day: Day02::DAY,
+                part,
+                answer: answer.to_string(),
+                elapsed: start.elapsed(),
+            };
+            record.print(options.format);
+        }
+    }
 
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let file = File::open(Path::new("./data/test")).unwrap();
-
-        let result = part_one(file);
-
-        assert_eq!(result, 2);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let file = File::open(Path::new("./data/test")).unwrap();
-
-        let result = part_two(file);
-
-        assert_eq!(result, 4);
-    }
+fn main() -> ExitCode {
+    common::exit(run(env::args().skip(1)))
 }
@@ -0,0 +1,552 @@
+use common::{ParseError, Solution, SolveError};
+
+mod stream;
+mod trace;
+
+pub use stream::{Stream, DEFAULT_BUFFER_LEN};
+pub use trace::{highlight, Step};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Token>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Vec<Token>, ParseError> {
+        Ok(InstructionSet::standard()
+            .tokenize(input.as_bytes())
+            .collect())
+    }
+
+    fn part_one(tokens: &Vec<Token>) -> Result<i64, SolveError> {
+        Ok(sum_products(tokens, false))
+    }
+
+    fn part_two(tokens: &Vec<Token>) -> Result<i64, SolveError> {
+        Ok(sum_products(tokens, true))
+    }
+}
+
+/// Most operands an instruction can take.
+pub const MAX_ARITY: usize = 4;
+
+/// State the instructions act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Machine {
+    pub total: i64,
+    /// Cleared by `don't()` and set by `do()` in the standard instruction set.
+    pub enabled: bool,
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Machine {
+            total: 0,
+            enabled: true,
+        }
+    }
+}
+
+/// What an instruction does to the machine, given its operands.
+pub type Semantics = fn(&mut Machine, &[u16]);
+
+/// An instruction that can appear in memory as `name(a,b,...)`.
+#[derive(Debug, Clone)]
+pub struct Opcode {
+    pub name: String,
+    pub arity: usize,
+    /// Whether the instruction is skipped while the machine is disabled.
+    pub gated: bool,
+    pub semantics: Semantics,
+}
+
+/// The instructions a scanner recognises, in the order they were registered.
+#[derive(Debug, Clone, Default)]
+pub struct InstructionSet {
+    opcodes: Vec<Opcode>,
+}
+
+impl InstructionSet {
+    pub fn new() -> Self {
+        InstructionSet::default()
+    }
+
+    /// The puzzle's instructions: `mul(a,b)`, `do()` and `don't()`.
+    pub fn standard() -> Self {
+        let mut set = InstructionSet::new();
+        set.register("mul", 2, true, |m, ops| {
+            m.total += i64::from(ops[0]) * i64::from(ops[1]);
+        });
+        set.register("do", 0, false, |m, _| m.enabled = true);
+        set.register("don't", 0, false, |m, _| m.enabled = false);
+        set
+    }
+
+    /// The standard set plus `add(a,b)`, `sub(a,b)` and `reset()`, which clears the total.
+    pub fn extended() -> Self {
+        let mut set = InstructionSet::standard();
+        set.register("add", 2, true, |m, ops| {
+            m.total += i64::from(ops[0]) + i64::from(ops[1]);
+        });
+        set.register("sub", 2, true, |m, ops| {
+            m.total += i64::from(ops[0]) - i64::from(ops[1]);
+        });
+        set.register("reset", 0, false, |m, _| m.total = 0);
+        set
+    }
+
+    /// Adds an instruction and returns its opcode.
+    ///
+    /// A `gated` instruction is skipped while the machine is disabled; the others always run.
+    /// When several instructions match at the same offset, the first registered one wins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is empty or `arity` is larger than [`MAX_ARITY`].
+    pub fn register(
+        &mut self,
+        name: &str,
+        arity: usize,
+        gated: bool,
+        semantics: Semantics,
+    ) -> usize {
+        assert!(!name.is_empty(), "instruction names cannot be empty");
+        assert!(
+            arity <= MAX_ARITY,
+            "`{name}` takes more than {MAX_ARITY} operands"
+        );
+
+        self.opcodes.push(Opcode {
+            name: name.to_string(),
+            arity,
+            gated,
+            semantics,
+        });
+        self.opcodes.len() - 1
+    }
+
+    pub fn opcode(&self, opcode: usize) -> &Opcode {
+        &self.opcodes[opcode]
+    }
+
+    /// The opcode `instruction` runs in this set, if the set has it.
+    ///
+    /// The typed instructions run the first opcode registered with their name and arity.
+    pub fn opcode_of(&self, instruction: &Instruction) -> Option<usize> {
+        let (name, arity) = match *instruction {
+            Instruction::Mul(..) => ("mul", 2),
+            Instruction::Do => ("do", 0),
+            Instruction::Dont => ("don't", 0),
+            Instruction::Custom { opcode, .. } => return Some(opcode),
+        };
+
+        self.opcodes
+            .iter()
+            .position(|op| op.name == name && op.arity == arity)
+    }
+
+    /// Writes `instruction` the way it appears in memory, as `name(a,b,...)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `instruction` is not in this set.
+    pub fn display(&self, instruction: &Instruction) -> String {
+        let operands: Vec<String> = instruction
+            .operands()
+            .as_slice()
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+
+        format!(
+            "{}({})",
+            self.opcode(self.expect_opcode(instruction)).name,
+            operands.join(",")
+        )
+    }
+
+    /// Scans `input` for the instructions of this set.
+    pub fn tokenize<'a>(&'a self, input: &'a [u8]) -> Tokenizer<'a> {
+        Tokenizer {
+            set: self,
+            input,
+            offset: 0,
+        }
+    }
+
+    /// Runs `instruction` on `machine`, unless it is gated and the machine is disabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `instruction` is not in this set.
+    pub fn execute(&self, machine: &mut Machine, instruction: &Instruction) {
+        let op = self.opcode(self.expect_opcode(instruction));
+        if op.gated && !machine.enabled {
+            return;
+        }
+        (op.semantics)(machine, instruction.operands().as_slice());
+    }
+
+    /// Runs `tokens` on a fresh machine and returns its total.
+    pub fn evaluate<'a>(&self, tokens: impl IntoIterator<Item = &'a Token>) -> i64 {
+        let mut machine = Machine::default();
+        for token in tokens {
+            self.execute(&mut machine, &token.instruction);
+        }
+        machine.total
+    }
+
+    /// The instruction at the very start of `input` and its length in bytes, if there is one.
+    fn instruction_at(&self, input: &[u8]) -> Option<(Instruction, usize)> {
+        self.opcodes.iter().enumerate().find_map(|(opcode, op)| {
+            let (operands, len) = op.parse(input)?;
+            let instruction = match (op.name.as_str(), &operands[..op.arity]) {
+                ("mul", &[a, b]) => Instruction::Mul(a, b),
+                ("do", []) => Instruction::Do,
+                ("don't", []) => Instruction::Dont,
+                (_, operands) => Instruction::Custom {
+                    opcode,
+                    operands: Operands::new(operands),
+                },
+            };
+            Some((instruction, len))
+        })
+    }
+
+    pub(crate) fn expect_opcode(&self, instruction: &Instruction) -> usize {
+        self.opcode_of(instruction)
+            .unwrap_or_else(|| panic!("{instruction:?} is not in this instruction set"))
+    }
+}
+
+impl Opcode {
+    /// Parses `name(a,b,...)` at the start of `input`, returning the operands and the length.
+    fn parse(&self, input: &[u8]) -> Option<([u16; MAX_ARITY], usize)> {
+        let mut rest = input
+            .strip_prefix(self.name.as_bytes())?
+            .strip_prefix(b"(")?;
+        let mut operands = [0; MAX_ARITY];
+
+        for (i, value) in operands.iter_mut().take(self.arity).enumerate() {
+            if i > 0 {
+                rest = rest.strip_prefix(b",")?;
+            }
+            (*value, rest) = operand(rest)?;
+        }
+
+        let rest = rest.strip_prefix(b")")?;
+        Some((operands, input.len() - rest.len()))
+    }
+}
+
+/// An instruction recognised in the corrupted memory.
+///
+/// The puzzle's own instructions are typed; any other instruction of an [`InstructionSet`] is
+/// [`Custom`](Self::Custom).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// `mul(a,b)`, with operands of one to three digits.
+    Mul(u16, u16),
+    /// `do()`
+    Do,
+    /// `don't()`
+    Dont,
+    /// Any other instruction, by its index in its [`InstructionSet`].
+    Custom { opcode: usize, operands: Operands },
+}
+
+impl Instruction {
+    /// The operands, as they appear in memory.
+    pub fn operands(&self) -> Operands {
+        match *self {
+            Instruction::Mul(a, b) => Operands::new(&[a, b]),
+            Instruction::Do | Instruction::Dont => Operands::new(&[]),
+            Instruction::Custom { operands, .. } => operands,
+        }
+    }
+}
+
+/// The operands of an instruction, each of one to three digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operands {
+    len: usize,
+    values: [u16; MAX_ARITY],
+}
+
+impl Operands {
+    /// Copies up to [`MAX_ARITY`] operands.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`MAX_ARITY`] operands.
+    pub fn new(operands: &[u16]) -> Self {
+        assert!(
+            operands.len() <= MAX_ARITY,
+            "instructions take at most {MAX_ARITY} operands, found {}",
+            operands.len()
+        );
+
+        let mut values = [0; MAX_ARITY];
+        values[..operands.len()].copy_from_slice(operands);
+        Operands {
+            len: operands.len(),
+            values,
+        }
+    }
+
+    pub fn as_slice(&self) -> &[u16] {
+        &self.values[..self.len]
+    }
+}
+
+/// An instruction and the bytes of the input it was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub offset: usize,
+    pub len: usize,
+    pub instruction: Instruction,
+}
+
+/// Scans corrupted memory for the instructions of an [`InstructionSet`], skipping everything
+/// else.
+///
+/// Like a regex search, matches are leftmost and do not overlap: after a match the scan resumes
+/// right behind it, otherwise one byte further.
+pub struct Tokenizer<'a> {
+    set: &'a InstructionSet,
+    input: &'a [u8],
+    offset: usize,
+}
+
+impl Iterator for Tokenizer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        while self.offset < self.input.len() {
+            let offset = self.offset;
+            match self.set.instruction_at(&self.input[offset..]) {
+                Some((instruction, len)) => {
+                    self.offset += len;
+                    return Some(Token {
+                        offset,
+                        len,
+                        instruction,
+                    });
+                }
+                None => self.offset += 1,
+            }
+        }
+        None
+    }
+}
+
+/// Splits a number of one to three digits off the start of `input`.
+fn operand(input: &[u8]) -> Option<(u16, &[u8])> {
+    let digits = input
+        .iter()
+        .take(3)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    if digits == 0 {
+        return None;
+    }
+
+    let value = input[..digits]
+        .iter()
+        .fold(0, |value, &b| value * 10 + u16::from(b - b'0'));
+
+    Some((value, &input[digits..]))
+}
+
+/// Adds up the products of the standard set's `mul` instructions in `tokens`, skipping those
+/// after a `don't()` until the next `do()` if `conditional`.
+pub fn sum_products(tokens: &[Token], conditional: bool) -> i64 {
+    let set = InstructionSet::standard();
+
+    set.evaluate(
+        tokens
+            .iter()
+            .filter(|token| conditional || matches!(token.instruction, Instruction::Mul(..))),
+    )
+}
+
+pub fn part_one(input: &str) -> i64 {
+    let tokens: Vec<Token> = InstructionSet::standard()
+        .tokenize(input.as_bytes())
+        .collect();
+
+    sum_products(&tokens, false)
+}
+
+pub fn part_two(input: &str) -> i64 {
+    let tokens: Vec<Token> = InstructionSet::standard()
+        .tokenize(input.as_bytes())
+        .collect();
+
+    sum_products(&tokens, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(include_str!("../data/test"));
+
+        assert_eq!(result, 161);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(include_str!("../data/test"));
+
+        assert_eq!(result, 48);
+    }
+
+    #[test]
+    fn test_tokenizer() {
+        let set = InstructionSet::standard();
+        let tokens: Vec<Token> = set
+            .tokenize(b"mulmul(1,2)do()mul(1234,5)mul(12,345)xdon't()mul(1,)")
+            .collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token {
+                    offset: 3,
+                    len: 8,
+                    instruction: Instruction::Mul(1, 2)
+                },
+                Token {
+                    offset: 11,
+                    len: 4,
+                    instruction: Instruction::Do
+                },
+                Token {
+                    offset: 26,
+                    len: 11,
+                    instruction: Instruction::Mul(12, 345)
+                },
+                Token {
+                    offset: 38,
+                    len: 7,
+                    instruction: Instruction::Dont
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_registered_instructions() {
+        let mut set = InstructionSet::extended();
+        set.register("neg", 1, false, |m, ops| m.total -= i64::from(ops[0]));
+        let input = b"add(1,2)mul(3,4)reset()sub(5,7)don't()add(9,9)neg(1)neg(2,3)";
+
+        let tokens: Vec<Token> = set.tokenize(input).collect();
+
+        assert_eq!(tokens.len(), 7);
+        assert_eq!(
+            tokens[0].instruction,
+            Instruction::Custom {
+                opcode: 3,
+                operands: Operands::new(&[1, 2])
+            }
+        );
+        assert_eq!(tokens[1].instruction, Instruction::Mul(3, 4));
+        assert_eq!(set.display(&tokens[0].instruction), "add(1,2)");
+        assert_eq!(set.evaluate(&tokens), -3);
+    }
+
+    #[test]
+    fn test_stream() {
+        let set = InstructionSet::standard();
+        let input = include_str!("../data/test").repeat(10);
+
+        let streamed: Vec<Token> = set
+            .stream(input.as_bytes(), 1)
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(set.max_len(), 12);
+        assert_eq!(streamed, set.tokenize(input.as_bytes()).collect::<Vec<_>>());
+        assert_eq!(
+            set.evaluate_stream(include_str!("../data/test").as_bytes(), DEFAULT_BUFFER_LEN)
+                .unwrap(),
+            (161, 48)
+        );
+        assert_eq!(
+            InstructionSet::extended()
+                .evaluate_stream(&b"mul(2,3)add(1,1)sub(5,1)"[..], 1)
+                .unwrap(),
+            (6, 12)
+        );
+
+        let mut set = InstructionSet::new();
+        set.register("add", 2, false, |m, ops| {
+            m.total += i64::from(ops[0]) + i64::from(ops[1])
+        });
+        set.register("mul", 2, false, |m, ops| {
+            m.total += i64::from(ops[0]) * i64::from(ops[1])
+        });
+
+        assert_eq!(
+            set.evaluate_stream(&b"add(1,2)mul(3,4)"[..], 1).unwrap(),
+            (12, 15)
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let set = InstructionSet::standard();
+        let input = include_str!("../data/test").as_bytes();
+        let tokens: Vec<Token> = set.tokenize(input).collect();
+
+        let steps = set.trace(&tokens);
+
+        let rows: Vec<(usize, bool, i64, i64)> = steps
+            .iter()
+            .map(|s| (s.token.offset, s.active, s.contribution, s.machine.total))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (1, true, 8, 8),
+                (20, true, 0, 8),
+                (28, false, 0, 8),
+                (48, false, 0, 8),
+                (59, true, 0, 8),
+                (64, true, 40, 48),
+            ]
+        );
+        assert_eq!(set.display(&steps[5].token.instruction), "mul(8,5)");
+
+        let extended = InstructionSet::extended();
+        let input = b"don't()add(1,2)add(0,0)sub(5,5)reset()do()";
+        let tokens: Vec<Token> = extended.tokenize(input).collect();
+
+        assert_eq!(
+            extended
+                .trace(&tokens)
+                .iter()
+                .map(|s| s.active)
+                .collect::<Vec<_>>(),
+            [true, false, false, false, true, true]
+        );
+
+        let tokens: Vec<Token> = set.tokenize(b"don't()mul(0,5)").collect();
+
+        assert!(!set.trace(&tokens)[1].active);
+
+        let input = b"xmul(1,2)don't()?mul(3,4)";
+        let tokens: Vec<Token> = set.tokenize(input).collect();
+
+        assert_eq!(
+            highlight(input, &set.trace(&tokens)),
+            "x\x1b[32mmul(1,2)\x1b[0m\x1b[32mdon't()\x1b[0m\x1b[2m?\x1b[0m\x1b[31mmul(3,4)\x1b[0m"
+        );
+    }
+}
@@ -1,92 +1,175 @@
-use regex::Regex;
+use common::{Format, Options, Record, Solution};
+use day03::{Day03, InstructionSet};
+use std::env;
 use std::error::Error;
-use std::fs;
-
-fn part_one(input: String) -> i32 {
-    let re = Regex::new(r"mul\(\d+,\d+\)").unwrap();
-
-    let inner_re = Regex::new(r"\d+").unwrap();
-
-    let muls: Vec<&str> = re.find_iter(&input).map(|m| m.as_str()).collect();
-
-    let mut result: i32 = 0;
-
-    for mul in muls {
-        let numbers: Vec<&str> = inner_re.find_iter(mul).map(|m| m.as_str()).collect();
-
-        if numbers.len() != 2 {
-            println!("ERROR FOR MUL {mul}");
+use std::fs::File;
+use std::io::{self, Read};
+use std::process::ExitCode;
+use std::time::Instant;
+
+const USAGE: &str = "usage: day03 [--part 1|2] [--format text|json] \
+    [--dialect standard|extended] [--trace | --highlight | --stream [--buffer N]] [FILE]";
+
+/// Prints every instruction of `path` with whether it was active and what it contributed.
+fn trace(path: &str, set: &InstructionSet, format: Format) -> Result<(), Box<dyn Error>> {
+    let input = common::read_input(path)?;
+    let tokens: Vec<_> = set.tokenize(input.as_bytes()).collect();
+
+    if format == Format::Text {
+        println!(
+            "{:>8}  {:<16}  {:<6}  {:>12}  {:>12}",
+            "offset", "instruction", "active", "contribution", "total"
+        );
+    }
+    for step in set.trace(&tokens) {
+        let instruction = set.display(&step.token.instruction);
+        match format {
+            Format::Text => println!(
+                "{:>8}  {:<16}  {:<6}  {:>12}  {:>12}",
+                step.token.offset,
+                instruction,
+                if step.active { "yes" } else { "no" },
+                step.contribution,
+                step.machine.total
+            ),
+            Format::Json => println!(
+                r#"{{"offset":{},"instruction":{},"active":{},"contribution":{},"total":{}}}"#,
+                step.token.offset,
+                common::json_string(&instruction),
+                step.active,
+                step.contribution,
+                step.machine.total
+            ),
         }
-
-        let sum: i32 = numbers[0].parse::<i32>().unwrap() * (numbers[1]).parse::<i32>().unwrap();
-
-        result += sum;
     }
-    result
-}
-fn part_two(input: String) -> i32 {
-    let re = Regex::new(r"(mul\(\d+,\d+\)|(do\(\))|(don't\(\)))").unwrap();
-
-    let inner_re = Regex::new(r"\d+").unwrap();
-
-    let instructions: Vec<&str> = re.find_iter(&input).map(|m| m.as_str()).collect();
-
-    let mut result: i32 = 0;
 
-    let mut enable: bool = true;
-
-    for ins in instructions {
-        match ins {
-            "do()" => enable = true,
-            "don't()" => enable = false,
-            _ => {
-                if enable {
-                    let numbers: Vec<&str> = inner_re.find_iter(ins).map(|m| m.as_str()).collect();
+    Ok(())
+}
 
-                    if numbers.len() != 2 {
-                        println!("ERROR FOR MUL {ins}");
-                    }
+/// Prints `path` with its enabled and disabled regions highlighted: see [`day03::highlight`].
+fn highlight(path: &str, set: &InstructionSet) -> Result<(), Box<dyn Error>> {
+    let input = common::read_input(path)?;
+    let tokens: Vec<_> = set.tokenize(input.as_bytes()).collect();
+
+    print!(
+        "{}",
+        day03::highlight(input.as_bytes(), &set.trace(&tokens))
+    );
+    if !input.ends_with('\n') {
+        println!();
+    }
 
-                    let sum: i32 =
-                        numbers[0].parse::<i32>().unwrap() * (numbers[1]).parse::<i32>().unwrap();
+    Ok(())
+}
 
-                    result += sum;
-                }
-            }
+/// Solves both parts in one pass over `path` without loading it into memory.
+fn stream(
+    path: &str,
+    set: &InstructionSet,
+    buffer_len: usize,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let reader: Box<dyn Read> = if path == common::STDIN {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(File::open(path).map_err(|e| format!("{path}: {e}"))?)
+    };
+
+    let start = Instant::now();
+    let (part_one, part_two) = set
+        .evaluate_stream(reader, buffer_len)
+        .map_err(|e| format!("{}: {e}", common::source_name(path)))?;
+    let elapsed = start.elapsed();
+
+    for (part, answer) in [(1, part_one), (2, part_two)] {
+        if options.wants(part) {
+            let record = Record {
+                day: Day03::DAY,
+                part,
+                answer: answer.to_string(),
+                elapsed,
+            };
+            record.print(options.format);
         }
     }
-    result
-}
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let input: String = fs::read_to_string("./data/input")?;
 
-    println!("Part 1: {}", part_one(input.clone()));
-    println!("Part 2: {}", part_two(input.clone()));
-
-    part_two(input);
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input: String = fs::read_to_string("./data/test").unwrap();
-
-        let result = part_one(input);
-
-        assert_eq!(result, 161);
+/// Evaluates `path` with every instruction of `set`, instead of solving the puzzle.
+fn evaluate(
+    path: &str,
+    dialect: &str,
+    set: &InstructionSet,
+    format: Format,
+) -> Result<(), Box<dyn Error>> {
+    let input = common::read_input(path)?;
+    let tokens: Vec<_> = set.tokenize(input.as_bytes()).collect();
+    let total = set.evaluate(&tokens);
+
+    match format {
+        Format::Text => println!("Total: {total}"),
+        Format::Json => println!(
+            r#"{{"day":3,"dialect":{},"total":{total}}}"#,
+            common::json_string(dialect)
+        ),
     }
 
-    #[test]
-    fn test_part_two() {
-        let input: String = fs::read_to_string("./data/test").unwrap();
+    Ok(())
+}
 
-        let result = part_two(input);
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut options = Options::default();
+    let mut dialect = String::from("standard");
+    let mut mode = None;
+    let mut buffer_len = day03::DEFAULT_BUFFER_LEN;
+    let mut files = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if arg == "--dialect" {
+            dialect = args
+                .next()
+                .ok_or("--dialect expects standard or extended")?;
+        } else if arg == "--trace" || arg == "--highlight" || arg == "--stream" {
+            mode = Some(arg);
+        } else if arg == "--buffer" {
+            buffer_len = args
+                .next()
+                .and_then(|n| n.parse().ok())
+                .ok_or("--buffer expects a number of bytes")?;
+        } else if !options.parse_flag(&arg, &mut args)? {
+            files.push(arg);
+        }
+    }
 
-        assert_eq!(result, 48);
+    let path = match files.as_slice() {
+        [] => common::STDIN,
+        [path] => path,
+        _ => return Err(USAGE.into()),
+    };
+
+    let set = match dialect.as_str() {
+        "standard" => InstructionSet::standard(),
+        "extended" => InstructionSet::extended(),
+        _ => {
+            return Err(
+                format!("unknown dialect `{dialect}`, expected standard or extended").into(),
+            )
+        }
+    };
+
+    match mode.as_deref() {
+        Some("--trace") => trace(path, &set, options.format),
+        Some("--stream") => stream(path, &set, buffer_len, &options),
+        Some(_) => highlight(path, &set),
+        None if dialect == "standard" => common::run_file::<Day03>(path, &options),
+        None if options.part.is_some() => {
+            Err(format!("--dialect {dialect} has no parts to pick from without --stream").into())
+        }
+        None => evaluate(path, &dialect, &set, options.format),
     }
 }
+
+fn main() -> ExitCode {
+    common::exit(run(env::args().skip(1)))
+}
@@ -0,0 +1,128 @@
+use crate::{Instruction, InstructionSet, Machine, Token};
+use std::io::{self, ErrorKind, Read};
+
+/// Size of the buffer [`InstructionSet::stream`] reads through by default.
+pub const DEFAULT_BUFFER_LEN: usize = 64 * 1024;
+
+impl InstructionSet {
+    /// Length in bytes of the longest instruction this set can match.
+    pub fn max_len(&self) -> usize {
+        self.opcodes
+            .iter()
+            .map(|op| op.name.len() + 2 + op.arity * 4 - op.arity.min(1))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Scans `reader` for instructions through a buffer of `buffer_len` bytes, which is grown
+    /// to twice [`max_len`](Self::max_len) if it is smaller.
+    pub fn stream<R: Read>(&self, reader: R, buffer_len: usize) -> Stream<'_, R> {
+        let max_len = self.max_len();
+        let buffer_len = buffer_len.max(2 * max_len).max(1);
+
+        Stream {
+            set: self,
+            max_len,
+            reader,
+            buffer: vec![0; buffer_len].into_boxed_slice(),
+            start: 0,
+            end: 0,
+            offset: 0,
+            eof: false,
+        }
+    }
+
+    /// Solves both parts in one pass over `reader` in constant memory.
+    ///
+    /// Part one only runs the [`Mul`](crate::Instruction::Mul) instructions, like
+    /// [`sum_products`](crate::sum_products); part two evaluates every instruction, like
+    /// [`evaluate`](Self::evaluate).
+    pub fn evaluate_stream(&self, reader: impl Read, buffer_len: usize) -> io::Result<(i64, i64)> {
+        let mut unconditional = Machine::default();
+        let mut conditional = Machine::default();
+
+        for token in self.stream(reader, buffer_len) {
+            let instruction = token?.instruction;
+            if let Instruction::Mul(..) = instruction {
+                self.execute(&mut unconditional, &instruction);
+            }
+            self.execute(&mut conditional, &instruction);
+        }
+
+        Ok((unconditional.total, conditional.total))
+    }
+}
+
+/// Like [`Tokenizer`](crate::Tokenizer), but reading the input through a fixed-size buffer.
+///
+/// The scan only moves on while at least [`InstructionSet::max_len`] bytes are buffered, or
+/// the input has ended, so an instruction straddling two reads is matched whole.
+pub struct Stream<'a, R> {
+    set: &'a InstructionSet,
+    /// [`InstructionSet::max_len`] of `set`.
+    max_len: usize,
+    reader: R,
+    buffer: Box<[u8]>,
+    /// Next byte of `buffer` to scan.
+    start: usize,
+    /// End of the bytes read into `buffer`.
+    end: usize,
+    /// Offset in the input of `buffer[0]`.
+    offset: usize,
+    eof: bool,
+}
+
+impl<R: Read> Stream<'_, R> {
+    /// Moves the unscanned bytes to the front of the buffer and reads until it is full or the
+    /// input ends.
+    fn refill(&mut self) -> io::Result<()> {
+        self.buffer.copy_within(self.start..self.end, 0);
+        self.offset += self.start;
+        self.end -= self.start;
+        self.start = 0;
+
+        while self.end < self.buffer.len() {
+            match self.reader.read(&mut self.buffer[self.end..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(n) => self.end += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for Stream<'_, R> {
+    type Item = io::Result<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.end - self.start < self.max_len && !self.eof {
+                if let Err(e) = self.refill() {
+                    return Some(Err(e));
+                }
+            }
+            if self.start == self.end {
+                return None;
+            }
+
+            let offset = self.offset + self.start;
+            match self.set.instruction_at(&self.buffer[self.start..self.end]) {
+                Some((instruction, len)) => {
+                    self.start += len;
+                    return Some(Ok(Token {
+                        offset,
+                        len,
+                        instruction,
+                    }));
+                }
+                None => self.start += 1,
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{InstructionSet, Machine, Token};
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+
+/// One instruction of a [`trace`](InstructionSet::trace) and what it did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub token: Token,
+    /// Whether the instruction ran: it was reached while instructions were enabled, or it is
+    /// not [`gated`](crate::Opcode::gated) on them, like `do()`, `don't()` and `reset()`.
+    pub active: bool,
+    /// How much the instruction changed the total.
+    pub contribution: i64,
+    /// The machine right after the instruction, with the running total.
+    pub machine: Machine,
+}
+
+impl InstructionSet {
+    /// Runs `tokens` on a fresh machine like [`evaluate`](Self::evaluate), recording every
+    /// instruction.
+    pub fn trace<'a>(&self, tokens: impl IntoIterator<Item = &'a Token>) -> Vec<Step> {
+        let mut machine = Machine::default();
+
+        tokens
+            .into_iter()
+            .map(|&token| {
+                let before = machine;
+                self.execute(&mut machine, &token.instruction);
+                let gated = self.opcode(self.expect_opcode(&token.instruction)).gated;
+
+                Step {
+                    token,
+                    active: before.enabled || !gated,
+                    contribution: machine.total - before.total,
+                    machine,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Renders `input` for a terminal: active instructions in green, inactive ones in red, and the
+/// rest of the memory dimmed wherever instructions are disabled.
+pub fn highlight(input: &[u8], steps: &[Step]) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut enabled = true;
+    let mut offset = 0;
+
+    for step in steps {
+        let Token {
+            offset: start, len, ..
+        } = step.token;
+
+        paint(
+            &mut out,
+            if enabled { "" } else { DIM },
+            &input[offset..start],
+        );
+        paint(
+            &mut out,
+            if step.active { GREEN } else { RED },
+            &input[start..start + len],
+        );
+
+        enabled = step.machine.enabled;
+        offset = start + len;
+    }
+    paint(&mut out, if enabled { "" } else { DIM }, &input[offset..]);
+
+    out
+}
+
+/// Appends `bytes` to `out` in `color`, or unstyled if `color` is empty.
+fn paint(out: &mut String, color: &str, bytes: &[u8]) {
+    if bytes.is_empty() {
+        return;
+    }
+    out.push_str(color);
+    out.push_str(&String::from_utf8_lossy(bytes));
+    if !color.is_empty() {
+        out.push_str(RESET);
+    }
+}
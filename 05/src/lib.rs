@@ -0,0 +1,291 @@
+use common::{parse_token, ParseError, Solution, SolveError};
+use std::fmt;
+use std::fmt::Formatter;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = (Rules, Pages);
+    type Answer = isize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_one((rules, pages): &Self::Input) -> Result<isize, SolveError> {
+        Ok(part_one(rules.clone(), pages.clone()))
+    }
+
+    fn part_two((rules, pages): &Self::Input) -> Result<isize, SolveError> {
+        Ok(part_two(rules.clone(), pages.clone()))
+    }
+}
+
+/// Reads the official puzzle input: see [`parse_input`].
+pub fn read_input(reader: impl BufRead) -> Result<(Rules, Pages), Box<dyn std::error::Error>> {
+    Ok(parse_input(&io::read_to_string(reader)?)?)
+}
+
+/// Parses the official puzzle input: the `X|Y` rules, a blank line, then the updates.
+pub fn parse_input(input: &str) -> Result<(Rules, Pages), ParseError> {
+    let mut offset = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            let rules = &input[..offset];
+            let pages = &input[offset + line.len()..];
+
+            return Ok((
+                rules.parse()?,
+                pages
+                    .parse()
+                    .map_err(|e: ParseError| e.offset_lines(i + 1))?,
+            ));
+        }
+        offset += line.len();
+    }
+
+    let line = input.lines().count() + 1;
+    let message = "expected a blank line between the rules and the updates";
+    Err(ParseError::new(line, 1, "", message))
+}
+
+#[derive(Debug, Clone)]
+pub struct Rules {
+    data: Vec<(isize, isize)>,
+}
+
+impl Rules {
+    pub fn new(data: Vec<(isize, isize)>) -> Self {
+        Rules { data }
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(io::read_to_string(reader)?.parse()?)
+    }
+
+    pub fn validate(&self, page: &[isize]) -> bool {
+        for &(first, second) in &self.data {
+            let first_index = page.iter().position(|&x| x == first);
+            let second_index = page.iter().position(|&x| x == second);
+            if let (Some(fi), Some(si)) = (first_index, second_index) {
+                if fi > si {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn apply_rule<'a>(&self, page: &'a mut Vec<isize>) -> &'a mut Vec<isize> {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &(first, second) in &self.data {
+                let first_index = page.iter().position(|&x| x == first);
+                let second_index = page.iter().position(|&x| x == second);
+                if let (Some(fi), Some(si)) = (first_index, second_index) {
+                    if fi > si {
+                        page.swap(fi, si);
+                        changed = true;
+                    }
+                }
+            }
+        }
+        page
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pages {
+    data: Vec<Vec<isize>>,
+}
+
+impl Pages {
+    pub fn new(data: Vec<Vec<isize>>) -> Self {
+        Pages { data }
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(io::read_to_string(reader)?.parse()?)
+    }
+
+    pub fn get_mid_value_sum(&self) -> isize {
+        let mut result = 0;
+        for datum in &self.data {
+            result += datum[datum.len() / 2]
+        }
+        result
+    }
+}
+
+impl FromStr for Rules {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let data: Vec<(isize, isize)> = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let Some((first, second)) = line.split_once('|') else {
+                    return Err(ParseError::at(
+                        i + 1,
+                        line,
+                        line,
+                        "expected a rule of the form X|Y",
+                    ));
+                };
+                Ok((
+                    parse_token(i + 1, line, first.trim())?,
+                    parse_token(i + 1, line, second.trim())?,
+                ))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Rules { data })
+    }
+}
+
+impl FromStr for Pages {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut data: Vec<Vec<isize>> = vec![];
+
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let numbers: Vec<isize> = line
+                .split(',')
+                .map(|num| parse_token(i + 1, line, num.trim()))
+                .collect::<Result<_, _>>()?;
+            data.push(numbers)
+        }
+
+        Ok(Pages { data })
+    }
+}
+
+impl fmt::Display for Pages {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, datum) in self.data.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "page: {}",
+                datum
+                    .iter()
+                    .map(|&x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, (first, second)) in self.data.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "Rule: {}|{}", first, second)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn part_one(rules: Rules, pages: Pages) -> isize {
+    let valid = find_pages(&rules, pages, true);
+    valid.get_mid_value_sum()
+}
+
+pub fn part_two(rules: Rules, pages: Pages) -> isize {
+    let invalid_pages: Vec<Vec<isize>> = pages
+        .data
+        .into_iter()
+        .filter(|page| !rules.validate(page))
+        .collect();
+
+    let mut corrected_pages: Vec<Vec<isize>> = Vec::new();
+
+    for mut page in invalid_pages {
+        rules.apply_rule(&mut page);
+        corrected_pages.push(page);
+    }
+
+    let corrected = Pages {
+        data: corrected_pages,
+    };
+
+    corrected.get_mid_value_sum()
+}
+
+pub fn find_pages(rules: &Rules, pages: Pages, valid: bool) -> Pages {
+    let mut valid_pages: Vec<Vec<isize>> = vec![];
+
+    for page in pages.data {
+        if rules.validate(&page) == valid {
+            valid_pages.push(page.clone());
+        }
+    }
+
+    Pages { data: valid_pages }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let (rules, pages) = parse_input(include_str!("../data/test")).unwrap();
+
+        let result = part_one(rules, pages);
+
+        assert_eq!(result, 143);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let (rules, pages) = read_input(include_str!("../data/test").as_bytes()).unwrap();
+
+        let result = part_two(rules, pages);
+
+        assert_eq!(result, 123);
+    }
+
+    #[test]
+    fn test_split_input() {
+        let input = include_str!("../data/test").replace('\n', "\r\n");
+        let (rules, updates) = input.split_once("\r\n\r\n").unwrap();
+
+        let rules: Rules = rules.parse().unwrap();
+        let pages: Pages = updates.parse().unwrap();
+
+        assert_eq!(part_one(rules, pages), 143);
+
+        let error = parse_input(&input.replace("61,13", "61;13")).unwrap_err();
+
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (27, 1, "61;13")
+        );
+    }
+
+    #[test]
+    fn test_spacing() {
+        let input = include_str!("../data/test").replace('|', " | ") + "\n\n";
+        let (rules, pages) = parse_input(&input).unwrap();
+
+        assert_eq!(part_one(rules, pages), 143);
+    }
+}
@@ -0,0 +1,248 @@
+use common::{ParseError, Solution, SolveError};
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Grid;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Grid, ParseError> {
+        input.parse()
+    }
+
+    fn part_one(grid: &Grid) -> Result<usize, SolveError> {
+        Ok(part_one(grid))
+    }
+
+    fn part_two(grid: &Grid) -> Result<usize, SolveError> {
+        Ok(part_two(grid))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub fn turn_right(&mut self) {
+        *self = match *self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        };
+    }
+
+    pub fn delta(&self) -> (isize, isize) {
+        match *self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    data: Vec<Vec<char>>,
+    guard_pos: (isize, isize),
+    direction: Direction,
+}
+
+impl FromStr for Grid {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+
+        let mut guard_pos = None;
+        let mut direction = Direction::Up;
+
+        for (x, row) in data.iter().enumerate() {
+            for (y, &char) in row.iter().enumerate() {
+                match char {
+                    '^' => {
+                        guard_pos = Some((x as isize, y as isize));
+                        direction = Direction::Up;
+                    }
+                    '>' => {
+                        guard_pos = Some((x as isize, y as isize));
+                        direction = Direction::Right;
+                    }
+                    'v' => {
+                        guard_pos = Some((x as isize, y as isize));
+                        direction = Direction::Down;
+                    }
+                    '<' => {
+                        guard_pos = Some((x as isize, y as isize));
+                        direction = Direction::Left;
+                    }
+                    '.' | '#' => {}
+                    _ => {
+                        let tile = char.to_string();
+                        return Err(ParseError::new(x + 1, y + 1, &tile, "unexpected tile"));
+                    }
+                }
+            }
+        }
+
+        let Some(guard_pos) = guard_pos else {
+            return Err(ParseError::new(
+                data.len().max(1),
+                1,
+                "",
+                "the map has no guard",
+            ));
+        };
+
+        Ok(Self {
+            data,
+            guard_pos,
+            direction,
+        })
+    }
+}
+
+impl Grid {
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(io::read_to_string(reader)?.parse()?)
+    }
+
+    pub fn guard_pos(&self) -> (isize, isize) {
+        self.guard_pos
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn height(&self) -> isize {
+        self.data.len() as isize
+    }
+
+    pub fn width(&self) -> isize {
+        self.data[0].len() as isize
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<char> {
+        self.data.get(x as usize)?.get(y as usize).copied()
+    }
+
+    pub fn get_distinct(&self) -> usize {
+        self.data
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter(|&&c| c == 'X')
+            .count()
+    }
+
+    pub fn move_guard(&mut self) -> bool {
+        let (dx, dy) = self.direction.delta();
+        let new_pos = (self.guard_pos.0 + dx, self.guard_pos.1 + dy);
+
+        if let Some(tile) = self.get(new_pos.0, new_pos.1) {
+            if tile == '#' {
+                self.direction.turn_right();
+                true
+            } else {
+                self.data[self.guard_pos.0 as usize][self.guard_pos.1 as usize] = 'X';
+                self.guard_pos = new_pos;
+                true
+            }
+        } else {
+            self.data[self.guard_pos.0 as usize][self.guard_pos.1 as usize] = 'X';
+            false
+        }
+    }
+
+    pub fn find_loop_positions(&self) -> usize {
+        let mut loop_count = 0;
+        for x in 0..self.height() {
+            for y in 0..self.width() {
+                if self.get(x, y) == Some('.')
+                    && (x, y) != self.guard_pos
+                    && self.check_loop_with_obstacle(x, y)
+                {
+                    loop_count += 1;
+                }
+            }
+        }
+        loop_count
+    }
+
+    pub fn check_loop_with_obstacle(&self, obstacle_x: isize, obstacle_y: isize) -> bool {
+        let mut current_pos = self.guard_pos;
+        let mut current_dir = self.direction;
+        let mut visited = HashSet::new();
+        let mut steps = 0;
+        let max_steps = self.height() * self.width() * 4; // Arbitrary large number
+
+        while steps < max_steps {
+            if !visited.insert((current_pos, current_dir)) {
+                return true; // Loop detected
+            }
+
+            let (dx, dy) = current_dir.delta();
+            let new_pos = (current_pos.0 + dx, current_pos.1 + dy);
+
+            if new_pos == (obstacle_x, obstacle_y) || self.get(new_pos.0, new_pos.1) == Some('#') {
+                current_dir.turn_right();
+            } else if self.get(new_pos.0, new_pos.1).is_none() {
+                return false; // Guard left the map
+            } else {
+                current_pos = new_pos;
+            }
+
+            steps += 1;
+        }
+
+        false
+    }
+}
+
+pub fn part_one(grid: &Grid) -> usize {
+    let mut grid = grid.clone();
+
+    while grid.move_guard() {}
+
+    grid.get_distinct()
+}
+
+pub fn part_two(grid: &Grid) -> usize {
+    grid.find_loop_positions()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let mut grid = Grid::from_reader(include_str!("../data/test").as_bytes()).unwrap();
+
+        while grid.move_guard() {}
+
+        let result = grid.get_distinct();
+
+        assert_eq!(result, 41);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let grid: Grid = include_str!("../data/test").parse().unwrap();
+
+        let result = grid.find_loop_positions();
+
+        assert_eq!(result, 6);
+    }
+}
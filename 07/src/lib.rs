@@ -0,0 +1,158 @@
+use common::{parse_token, ParseError, Solution, SolveError};
+use std::error::Error;
+use std::io::{self, BufRead};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<Calibration>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_calibrations(input)
+    }
+
+    fn part_one(calibrations: &Self::Input) -> Result<i64, SolveError> {
+        part_one(calibrations.clone())
+    }
+
+    fn part_two(calibrations: &Self::Input) -> Result<i64, SolveError> {
+        part_two(calibrations.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Calibration {
+    pub test: i64,
+    pub equation: Vec<i64>,
+}
+
+pub fn test_calibration(cal: &Calibration, include_concat: bool) -> Result<bool, SolveError> {
+    if cal.equation.len() == 1 {
+        return Ok(cal.test == cal.equation[0]);
+    }
+
+    let base: u64 = if include_concat { 3 } else { 2 };
+
+    let n = cal.equation.len() - 1;
+    let combinations = u32::try_from(n)
+        .ok()
+        .and_then(|n| base.checked_pow(n))
+        .ok_or_else(|| SolveError::new(format!("{}: too many numbers to try", cal.test)))?;
+    for mask in 0..combinations {
+        let mut ops = mask;
+        let mut result = cal.equation[0];
+        for &number in &cal.equation[1..] {
+            let next = match ops % base {
+                0 => result.checked_add(number),
+                1 => result.checked_mul(number),
+                _ => concatenate(result, number),
+            };
+            result = next.ok_or_else(|| overflow(cal))?;
+            ops /= base;
+        }
+        if result == cal.test {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// The digits of `a` followed by those of `b`, if that is a number that fits in an `i64`.
+pub fn concatenate(a: i64, b: i64) -> Option<i64> {
+    format!("{a}{b}").parse().ok()
+}
+
+fn overflow(cal: &Calibration) -> SolveError {
+    SolveError::new(format!("{}: the equation overflows i64", cal.test))
+}
+
+pub fn read_calibrations(reader: impl BufRead) -> Result<Vec<Calibration>, Box<dyn Error>> {
+    Ok(parse_calibrations(&io::read_to_string(reader)?)?)
+}
+
+pub fn parse_calibrations(input: &str) -> Result<Vec<Calibration>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let malformed = |message| ParseError::at(i + 1, line, line, message);
+
+            let Some((test, equation)) = line.split_once(':') else {
+                return Err(malformed("expected `test: numbers...`"));
+            };
+            let test = parse_token(i + 1, line, test.trim())?;
+            let equation: Vec<i64> = equation
+                .split_whitespace()
+                .map(|n| parse_token(i + 1, line, n))
+                .collect::<Result<_, _>>()?;
+            if equation.is_empty() {
+                return Err(malformed("the equation has no numbers"));
+            }
+            Ok(Calibration { test, equation })
+        })
+        .collect()
+}
+
+pub fn part_one(calibrations: Vec<Calibration>) -> Result<i64, SolveError> {
+    total_calibration(calibrations, false)
+}
+
+pub fn part_two(calibrations: Vec<Calibration>) -> Result<i64, SolveError> {
+    total_calibration(calibrations, true)
+}
+
+fn total_calibration(
+    calibrations: Vec<Calibration>,
+    include_concat: bool,
+) -> Result<i64, SolveError> {
+    let mut result: i64 = 0;
+    for calibration in calibrations {
+        if test_calibration(&calibration, include_concat)? {
+            result = result
+                .checked_add(calibration.test)
+                .ok_or_else(|| SolveError::new("the total calibration result overflows i64"))?;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let calibrations = read_calibrations(include_str!("../data/test").as_bytes()).unwrap();
+        let result = part_one(calibrations).unwrap();
+        assert_eq!(result, 3749);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let calibrations = parse_calibrations(include_str!("../data/test")).unwrap();
+
+        let result = part_two(calibrations).unwrap();
+
+        assert_eq!(result, 11387);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let calibrations = parse_calibrations("1: 999999999999 9999999999").unwrap();
+
+        assert_eq!(
+            part_two(calibrations),
+            Err(SolveError::new("1: the equation overflows i64"))
+        );
+
+        let calibrations = parse_calibrations(&format!("1: {}", "1 ".repeat(42))).unwrap();
+
+        assert_eq!(
+            part_two(calibrations),
+            Err(SolveError::new("1: too many numbers to try"))
+        );
+    }
+}
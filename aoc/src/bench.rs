@@ -0,0 +1,199 @@
+use crate::DayCommand;
+use common::Solution;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The separately timed steps of solving a day.
+pub const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Mean, median and standard deviation of repeated timings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+
+        let n = nanos.len().max(1) as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        let median = match nanos.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (nanos[len / 2 - 1] + nanos[len / 2]) / 2.0,
+            len => nanos[len / 2],
+        };
+
+        Stats {
+            mean: Duration::from_nanos(mean as u64),
+            median: Duration::from_nanos(median as u64),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "mean {:>10.3?}  median {:>10.3?}  stddev {:>10.3?}",
+            self.mean, self.median, self.stddev
+        )
+    }
+}
+
+/// Timings of one stage of one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub stage: &'static str,
+    pub stats: Stats,
+}
+
+/// Times parsing and both parts of a day over repeated runs on `NN/data/<data>`.
+pub struct Bench {
+    pub data: String,
+    pub runs: usize,
+}
+
+impl DayCommand for Bench {
+    type Output = Vec<Measurement>;
+
+    fn call<S: Solution>(&self) -> Result<Vec<Measurement>, Box<dyn Error>> {
+        let path = format!("{:02}/data/{}", S::DAY, self.data);
+        let text = common::read_input(&path)?;
+        let input = S::parse(&text).map_err(|e| e.with_file(path.as_str()))?;
+        common::time_part::<S>(&input, 1)?;
+        common::time_part::<S>(&input, 2)?;
+
+        let parse = sample(self.runs, || {
+            black_box(S::parse(black_box(&text)).ok());
+        });
+        let part_one = sample(self.runs, || {
+            black_box(S::part_one(black_box(&input)).ok());
+        });
+        let part_two = sample(self.runs, || {
+            black_box(S::part_two(black_box(&input)).ok());
+        });
+
+        Ok([parse, part_one, part_two]
+            .into_iter()
+            .zip(STAGES)
+            .map(|(samples, stage)| Measurement {
+                day: S::DAY,
+                stage,
+                stats: Stats::from_samples(&samples),
+            })
+            .collect())
+    }
+}
+
+fn sample(runs: usize, mut f: impl FnMut()) -> Vec<Duration> {
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Saved measurements to compare new runs against, one `day stage mean_ns median_ns stddev_ns`
+/// line per measurement.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Baseline {
+    measurements: Vec<(u8, String, Stats)>,
+}
+
+impl Baseline {
+    pub fn load(path: &str) -> Result<Baseline, Box<dyn Error>> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        let mut measurements = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [day, stage, mean, median, stddev] = fields[..] else {
+                return Err(format!("{path}:{}: expected 5 fields, found `{line}`", i + 1).into());
+            };
+            let nanos = |field: &str| -> Result<Duration, Box<dyn Error>> {
+                let nanos = field
+                    .parse()
+                    .map_err(|e| format!("{path}:{}: {e} (found `{field}`)", i + 1))?;
+                Ok(Duration::from_nanos(nanos))
+            };
+
+            let stats = Stats {
+                mean: nanos(mean)?,
+                median: nanos(median)?,
+                stddev: nanos(stddev)?,
+            };
+            let day = day
+                .parse()
+                .map_err(|e| format!("{path}:{}: {e} (found `{day}`)", i + 1))?;
+            measurements.push((day, stage.to_string(), stats));
+        }
+
+        Ok(Baseline { measurements })
+    }
+
+    pub fn save(path: &str, measurements: &[Measurement]) -> Result<(), Box<dyn Error>> {
+        let mut text = String::from("# day stage mean_ns median_ns stddev_ns\n");
+        for m in measurements {
+            text.push_str(&format!(
+                "{} {} {} {} {}\n",
+                m.day,
+                m.stage,
+                m.stats.mean.as_nanos(),
+                m.stats.median.as_nanos(),
+                m.stats.stddev.as_nanos()
+            ));
+        }
+
+        Ok(fs::write(path, text).map_err(|e| format!("{path}: {e}"))?)
+    }
+
+    pub fn get(&self, day: u8, stage: &str) -> Option<&Stats> {
+        self.measurements
+            .iter()
+            .find(|(d, s, _)| *d == day && s == stage)
+            .map(|(_, _, stats)| stats)
+    }
+}
+
+/// Change of the median relative to `baseline`, e.g. `-12.5%`.
+pub fn change(stats: &Stats, baseline: &Stats) -> String {
+    let old = baseline.median.as_nanos() as f64;
+    if old == 0.0 {
+        return "n/a".to_string();
+    }
+    let new = stats.median.as_nanos() as f64;
+    format!("{:+.1}%", (new - old) / old * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_nanos);
+
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(stats.mean, Duration::from_nanos(2));
+        assert_eq!(stats.median, Duration::from_nanos(2));
+        assert_eq!(stats.stddev, Duration::from_nanos(1));
+    }
+}
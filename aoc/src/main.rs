@@ -0,0 +1,190 @@
+mod bench;
+mod verify;
+
+use bench::{Baseline, Bench};
+use common::{Options, Solution};
+use std::env;
+use std::error::Error;
+use std::process::ExitCode;
+use verify::{Summary, Verify};
+
+const USAGE: &str = "usage:
+    aoc run <day> [--part 1|2] [--format text|json] [--input PATH|-] [--rules PATH|-]
+    aoc verify [day]
+    aoc bench [day] [--data NAME] [--runs N] [--baseline FILE] [--save FILE]";
+
+const DAYS: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+
+/// Something to do with a day's solution, whatever its input and answer types.
+trait DayCommand {
+    type Output;
+
+    fn call<S: Solution>(&self) -> Result<Self::Output, Box<dyn Error>>;
+}
+
+fn for_day<C: DayCommand>(day: u8, command: &C) -> Result<C::Output, Box<dyn Error>> {
+    match day {
+        1 => command.call::<day01::Day01>(),
+        2 => command.call::<day02::Day02>(),
+        3 => command.call::<day03::Day03>(),
+        4 => command.call::<day04::Day04>(),
+        5 => command.call::<day05::Day05>(),
+        6 => command.call::<day06::Day06>(),
+        7 => command.call::<day07::Day07>(),
+        day => Err(format!("day {day} is not solved yet").into()),
+    }
+}
+
+fn parse_day(arg: Option<String>) -> Result<u8, Box<dyn Error>> {
+    let arg = arg.ok_or(USAGE)?;
+    arg.parse()
+        .map_err(|_| format!("`{arg}` is not a day number").into())
+}
+
+struct RunArgs {
+    day: u8,
+    options: Options,
+    input: String,
+    rules: Option<String>,
+}
+
+impl RunArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, Box<dyn Error>> {
+        let day = parse_day(args.next())?;
+
+        let mut options = Options::default();
+        let mut input = None;
+        let mut rules = None;
+
+        while let Some(arg) = args.next() {
+            if options.parse_flag(&arg, &mut args)? {
+                continue;
+            }
+            match arg.as_str() {
+                "--input" => input = Some(args.next().ok_or("--input expects a path")?),
+                "--rules" => rules = Some(args.next().ok_or("--rules expects a path")?),
+                _ => return Err(format!("unknown argument `{arg}`\n{USAGE}").into()),
+            }
+        }
+
+        let input = input.unwrap_or_else(|| format!("{day:02}/data/input"));
+
+        Ok(RunArgs {
+            day,
+            options,
+            input,
+            rules,
+        })
+    }
+}
+
+impl DayCommand for RunArgs {
+    type Output = ();
+
+    fn call<S: Solution>(&self) -> Result<(), Box<dyn Error>> {
+        common::run_file::<S>(&self.input, &self.options)
+    }
+}
+
+fn run(args: &RunArgs) -> Result<(), Box<dyn Error>> {
+    match (args.day, &args.rules) {
+        (5, Some(rules)) => {
+            let rules = common::parse_file(rules)?;
+            let pages = common::parse_file(&args.input)?;
+            common::solve::<day05::Day05>(&(rules, pages), &args.options)
+        }
+        (_, Some(_)) => Err("--rules only applies to day 5".into()),
+        (day, None) => for_day(day, args),
+    }
+}
+
+fn verify(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let days = match args.next() {
+        Some(day) => vec![parse_day(Some(day))?],
+        None => DAYS.to_vec(),
+    };
+
+    let mut summary = Summary::default();
+    for day in days {
+        summary.add(for_day(day, &Verify)?);
+    }
+
+    println!(
+        "{} passed, {} failed, {} unverified",
+        summary.passed, summary.failed, summary.unverified
+    );
+
+    if summary.failed > 0 {
+        return Err(format!("{} answer(s) failed verification", summary.failed).into());
+    }
+    Ok(())
+}
+
+fn bench(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut days = DAYS.to_vec();
+    let mut command = Bench {
+        data: "input".to_string(),
+        runs: 10,
+    };
+    let mut baseline = None;
+    let mut save = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--data" => command.data = args.next().ok_or("--data expects a file name")?,
+            "--runs" => {
+                command.runs = args.next().ok_or("--runs expects a number")?.parse()?;
+                if command.runs == 0 {
+                    return Err("--runs must be at least 1".into());
+                }
+            }
+            "--baseline" => baseline = Some(args.next().ok_or("--baseline expects a path")?),
+            "--save" => save = Some(args.next().ok_or("--save expects a path")?),
+            _ if !arg.starts_with("--") => days = vec![parse_day(Some(arg))?],
+            _ => return Err(format!("unknown argument `{arg}`\n{USAGE}").into()),
+        }
+    }
+
+    let baseline = baseline.as_deref().map(Baseline::load).transpose()?;
+
+    let mut measurements = Vec::new();
+    let mut failed = 0;
+    for day in days {
+        match for_day(day, &command) {
+            Ok(day_measurements) => measurements.extend(day_measurements),
+            Err(e) => {
+                println!("day {day:02}: error: {e}");
+                failed += 1;
+            }
+        }
+    }
+
+    for m in &measurements {
+        let compared = baseline
+            .as_ref()
+            .and_then(|baseline| baseline.get(m.day, m.stage))
+            .map(|old| format!("  {} vs baseline", bench::change(&m.stats, old)))
+            .unwrap_or_default();
+        println!("day {:02} {:<5}  {}{compared}", m.day, m.stage, m.stats);
+    }
+
+    if let Some(path) = save {
+        Baseline::save(&path, &measurements)?;
+    }
+
+    if failed > 0 {
+        return Err(format!("{failed} day(s) could not be benchmarked").into());
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+
+    common::exit(match args.next().as_deref() {
+        Some("run") => RunArgs::parse(args).and_then(|args| run(&args)),
+        Some("verify") => verify(args),
+        Some("bench") => bench(args),
+        _ => Err(USAGE.into()),
+    })
+}
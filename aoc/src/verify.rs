@@ -0,0 +1,86 @@
+use crate::DayCommand;
+use common::{KnownAnswers, Solution};
+use std::error::Error;
+use std::path::Path;
+
+/// Outcome of checking solvers against their `answers.toml`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    /// Parts solved without an accepted answer to compare against.
+    pub unverified: usize,
+}
+
+impl Summary {
+    pub fn add(&mut self, other: Summary) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.unverified += other.unverified;
+    }
+}
+
+/// Runs a day's solvers on every input listed in its `answers.toml` and compares the results.
+pub struct Verify;
+
+impl DayCommand for Verify {
+    type Output = Summary;
+
+    fn call<S: Solution>(&self) -> Result<Summary, Box<dyn Error>> {
+        let dir = format!("{:02}", S::DAY);
+        let mut summary = Summary::default();
+
+        let answers_path = format!("{dir}/answers.toml");
+        if !Path::new(&answers_path).exists() {
+            println!("day {dir}: no answers.toml, skipped");
+            return Ok(summary);
+        }
+        let answers: KnownAnswers = common::parse_file(&answers_path)?;
+
+        for name in answers.inputs() {
+            let path = format!("{dir}/data/{name}");
+            let input = match common::read_input(&path)
+                .and_then(|input| S::parse(&input).map_err(|e| e.with_file(path.as_str()).into()))
+            {
+                Ok(input) => input,
+                Err(e) => {
+                    println!("day {dir} {name}: error: {e}");
+                    summary.failed += 1;
+                    continue;
+                }
+            };
+
+            for part in [1, 2] {
+                let label = format!("day {dir} {name} part {part}");
+                let record = match common::time_part::<S>(&input, part) {
+                    Ok(record) => record,
+                    Err(e) => {
+                        println!("{label}: error: {e}");
+                        summary.failed += 1;
+                        continue;
+                    }
+                };
+
+                match answers.expected(name, part) {
+                    Some(expected) if expected == record.answer => {
+                        println!("{label}: pass ({expected})");
+                        summary.passed += 1;
+                    }
+                    Some(expected) => {
+                        println!(
+                            "{label}: REGRESSION, expected {expected} but got {}",
+                            record.answer
+                        );
+                        summary.failed += 1;
+                    }
+                    None => {
+                        println!("{label}: unverified, got {}", record.answer);
+                        summary.unverified += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::ParseError;
+use std::str::FromStr;
+
+/// Accepted answers recorded in a day's `answers.toml`.
+///
+/// Each section is named after an input file in the day's `data/` directory and holds
+/// the answers accepted for it:
+///
+/// ```toml
+/// [input]
+/// part1 = 1830467
+/// part2 = "26674158"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownAnswers {
+    inputs: Vec<(String, [Option<String>; 2])>,
+}
+
+impl KnownAnswers {
+    /// Names of every input file with a section, in file order, whether or not it has any
+    /// answers recorded yet.
+    pub fn inputs(&self) -> impl Iterator<Item = &str> {
+        self.inputs.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn expected(&self, input: &str, part: u8) -> Option<&str> {
+        let (_, answers) = self.inputs.iter().find(|(name, _)| name == input)?;
+        answers.get(usize::from(part).checked_sub(1)?)?.as_deref()
+    }
+}
+
+impl FromStr for KnownAnswers {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut inputs: Vec<(String, [Option<String>; 2])> = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let content = line.split('#').next().unwrap_or_default().trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = content.strip_prefix('[').and_then(|c| c.strip_suffix(']')) {
+                inputs.push((name.trim().to_string(), [None, None]));
+                continue;
+            }
+
+            let Some((key, value)) = content.split_once('=') else {
+                return Err(ParseError::at(
+                    i + 1,
+                    line,
+                    content,
+                    "expected `key = value`",
+                ));
+            };
+            let key = key.trim();
+            let value = value.trim();
+
+            let slot = match key {
+                "part1" => 0,
+                "part2" => 1,
+                _ => return Err(ParseError::at(i + 1, line, key, "expected part1 or part2")),
+            };
+            let value = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                Some(quoted) => quoted,
+                None if value.parse::<i128>().is_ok() => value,
+                None => {
+                    let message = "expected an integer or a quoted string";
+                    return Err(ParseError::at(i + 1, line, value, message));
+                }
+            };
+            let Some((_, answers)) = inputs.last_mut() else {
+                let message = "answers must follow an [input] section header";
+                return Err(ParseError::at(i + 1, line, key, message));
+            };
+
+            answers[slot] = Some(value.to_string());
+        }
+
+        Ok(KnownAnswers { inputs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_known_answers() {
+        let answers: KnownAnswers =
+            "# comment\n[test]\npart1 = 11\npart2 = \"31\" # ok\n\n[input]\n"
+                .parse()
+                .unwrap();
+
+        assert_eq!(answers.inputs().collect::<Vec<_>>(), ["test", "input"]);
+        assert_eq!(answers.expected("test", 1), Some("11"));
+        assert_eq!(answers.expected("test", 2), Some("31"));
+        assert_eq!(answers.expected("input", 1), None);
+
+        let error = "[test]\npart3 = 1".parse::<KnownAnswers>().unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
@@ -0,0 +1,115 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A malformed piece of puzzle input, located by line and column (both 1-based).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> Self {
+        ParseError {
+            file: None,
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Reports `token`, which must be a slice of `line`, at its position within that line.
+    pub fn at(line_no: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        let column = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&offset| offset <= line.len())
+            .map_or(1, |offset| line[..offset].chars().count() + 1);
+
+        ParseError::new(line_no, column, token, message)
+    }
+
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Moves the error down by `lines`, for sections parsed separately from the rest of a file.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        if !self.text.is_empty() {
+            write!(f, " (found `{}`)", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// A well-formed input for which a solver could not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    pub message: String,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> Self {
+        SolveError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for SolveError {}
+
+/// Parses `token`, a slice of `line`, reporting its position if it is not a valid `T`.
+pub fn parse_token<T>(line_no: usize, line: &str, token: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    token
+        .parse()
+        .map_err(|e: T::Err| ParseError::at(line_no, line, token, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_column_of_token() {
+        let line = "12   x4";
+        let token = line.split_whitespace().nth(1).unwrap();
+
+        let error = parse_token::<u32>(3, line, token)
+            .unwrap_err()
+            .with_file("data/input");
+
+        assert_eq!(error.column, 6);
+        assert_eq!(
+            error.to_string(),
+            "data/input:3:6: invalid digit found in string (found `x4`)"
+        );
+    }
+}
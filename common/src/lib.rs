@@ -0,0 +1,159 @@
+mod answers;
+mod error;
+mod output;
+
+pub use answers::KnownAnswers;
+pub use error::{parse_token, ParseError, SolveError};
+pub use output::{json_string, Format, Options, Record};
+
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Instant;
+
+/// Path that stands for stdin wherever an input file is expected.
+pub const STDIN: &str = "-";
+
+/// A day's puzzle: the input text is parsed once and both parts are solved from it.
+pub trait Solution {
+    /// Day of the advent calendar this solution belongs to.
+    const DAY: u8;
+
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+
+    /// Answer produced by either part.
+    type Answer: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_one(input: &Self::Input) -> Result<Self::Answer, SolveError>;
+
+    fn part_two(input: &Self::Input) -> Result<Self::Answer, SolveError>;
+}
+
+/// Parses `input` and prints the answers selected by `options`.
+///
+/// `file` names the source of `input` in parse errors.
+pub fn run<S: Solution>(
+    input: &str,
+    file: Option<&str>,
+    options: &Options,
+) -> Result<(), Box<dyn Error>> {
+    let input = S::parse(input).map_err(|e| match file {
+        Some(file) => e.with_file(file),
+        None => e,
+    })?;
+
+    solve::<S>(&input, options)
+}
+
+/// Prints the answers selected by `options` for an already parsed input.
+pub fn solve<S: Solution>(input: &S::Input, options: &Options) -> Result<(), Box<dyn Error>> {
+    for part in [1, 2] {
+        if options.wants(part) {
+            time_part::<S>(input, part)?.print(options.format);
+        }
+    }
+
+    Ok(())
+}
+
+/// Solves one part of an already parsed input, timing the solver.
+pub fn time_part<S: Solution>(input: &S::Input, part: u8) -> Result<Record, SolveError> {
+    let start = Instant::now();
+    let answer = match part {
+        1 => S::part_one(input),
+        _ => S::part_two(input),
+    }
+    .map_err(|e| SolveError::new(format!("day {} part {part}: {e}", S::DAY)))?;
+
+    Ok(Record {
+        day: S::DAY,
+        part,
+        answer: answer.to_string(),
+        elapsed: start.elapsed(),
+    })
+}
+
+/// Reads the puzzle input at `path`, or stdin when `path` is [`STDIN`].
+pub fn read_input(path: &str) -> Result<String, Box<dyn Error>> {
+    let input = if path == STDIN {
+        io::read_to_string(io::stdin().lock())
+    } else {
+        fs::read_to_string(path)
+    };
+
+    Ok(input.map_err(|e| format!("{}: {e}", source_name(path)))?)
+}
+
+/// Name used for `path` in diagnostics.
+pub fn source_name(path: &str) -> &str {
+    if path == STDIN {
+        "<stdin>"
+    } else {
+        path
+    }
+}
+
+/// Reads the puzzle input at `path` (see [`read_input`]), then behaves like [`run`].
+pub fn run_file<S: Solution>(path: &str, options: &Options) -> Result<(), Box<dyn Error>> {
+    let input = read_input(path)?;
+
+    run::<S>(&input, Some(source_name(path)), options)
+}
+
+/// Reads and parses the file at `path` (see [`read_input`]), naming it in any parse error.
+pub fn parse_file<T>(path: &str) -> Result<T, Box<dyn Error>>
+where
+    T: FromStr<Err = ParseError>,
+{
+    let input = read_input(path)?;
+
+    Ok(input
+        .parse()
+        .map_err(|e: ParseError| e.with_file(source_name(path)))?)
+}
+
+/// Splits the arguments of a per-day binary into its [`Options`] and its input files.
+pub fn parse_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<(Options, Vec<String>), Box<dyn Error>> {
+    let mut options = Options::default();
+    let mut files = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if !options.parse_flag(&arg, &mut args)? {
+            files.push(arg);
+        }
+    }
+
+    Ok((options, files))
+}
+
+/// Entry point of the per-day binaries: `dayNN [--part 1|2] [--format text|json] [FILE]`
+/// solves FILE, or stdin when it is missing or `-`.
+pub fn main<S: Solution>() -> ExitCode {
+    exit(
+        parse_args(env::args().skip(1)).and_then(|(options, files)| match files.as_slice() {
+            [] => run_file::<S>(STDIN, &options),
+            [path] => run_file::<S>(path, &options),
+            _ => Err("expected at most one input file".into()),
+        }),
+    )
+}
+
+/// Prints `result`'s error, if any, as a diagnostic on stderr instead of a `Debug` dump.
+pub fn exit(result: Result<(), Box<dyn Error>>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How answers are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// `Part 1: 41`, for people.
+    #[default]
+    Text,
+    /// One JSON object per line, for scripts and dashboards.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format `{s}`, expected text or json")),
+        }
+    }
+}
+
+/// The answer to one part of a day, with the time it took to compute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+impl Record {
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Text => println!("Part {}: {}", self.part, self.answer),
+            Format::Json => println!("{}", self.to_json()),
+        }
+    }
+
+    /// Renders the record as `{"day":6,"part":1,"answer":41,"elapsed_ns":1234}`.
+    ///
+    /// Integer answers are emitted as JSON numbers, anything else as a string.
+    pub fn to_json(&self) -> String {
+        let answer = if self.answer.parse::<i128>().is_ok() {
+            self.answer.clone()
+        } else {
+            json_string(&self.answer)
+        };
+
+        format!(
+            r#"{{"day":{},"part":{},"answer":{},"elapsed_ns":{}}}"#,
+            self.day,
+            self.part,
+            answer,
+            self.elapsed.as_nanos()
+        )
+    }
+}
+
+/// Quotes `s` as a JSON string.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Command-line options shared by the `aoc` runner and the per-day binaries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Only solve this part, instead of both.
+    pub part: Option<u8>,
+    pub format: Format,
+}
+
+impl Options {
+    /// Consumes `arg`, and its value from `args`, if it is `--part` or `--format`.
+    pub fn parse_flag(
+        &mut self,
+        arg: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<bool, Box<dyn Error>> {
+        match arg {
+            "--part" => match args.next().as_deref() {
+                Some("1") => self.part = Some(1),
+                Some("2") => self.part = Some(2),
+                _ => return Err("--part expects 1 or 2".into()),
+            },
+            "--format" => {
+                self.format = args
+                    .next()
+                    .ok_or("--format expects text or json")?
+                    .parse()?
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    pub fn wants(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_record() {
+        let mut record = Record {
+            day: 6,
+            part: 1,
+            answer: "41".to_string(),
+            elapsed: Duration::from_nanos(1500),
+        };
+
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":6,"part":1,"answer":41,"elapsed_ns":1500}"#
+        );
+
+        record.answer = "a \"b\"".to_string();
+
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":6,"part":1,"answer":"a \"b\"","elapsed_ns":1500}"#
+        );
+    }
+}